@@ -2,22 +2,42 @@ use serde::Serialize;
 use serde_json::{ser::CompactFormatter, Value};
 use std::io;
 
-/// Serializes a given Yy file.
-#[cfg(target_os = "macos")]
+/// Serializes a given Yy file, with `\n` line endings, as the IDE writes
+/// them on every platform except Windows.
+#[cfg(not(target_os = "windows"))]
 pub fn serialize_file<T: Serialize + 'static>(value: &T) -> String {
-    ser(value)
+    ser(value, is_sprite::<T>())
 }
 
-/// Serializes a given Yy file.
+/// Serializes a given Yy file, with `\r\n` line endings, as the IDE writes
+/// them on Windows.
 #[cfg(target_os = "windows")]
 pub fn serialize_file<T: Serialize + 'static>(value: &T) -> String {
-    ser(value).replace('\n', "\r\n")
+    ser(value, is_sprite::<T>()).replace('\n', "\r\n")
 }
 
-fn ser<T: Serialize + 'static>(value: &T) -> String {
+/// Serializes a Yy file which was read as untyped JSON, with the same line
+/// endings as [`serialize_file`]. A sprite is laid out as it would be from a
+/// [`Sprite`](crate::Sprite), but keeps any fields the typings do not know.
+pub(crate) fn serialize_untyped_file(value: &Value) -> String {
+    let sprite = value.get("resourceType").and_then(Value::as_str) == Some("GMSprite");
+    let output = ser(value, sprite);
+
+    if cfg!(target_os = "windows") {
+        output.replace('\n', "\r\n")
+    } else {
+        output
+    }
+}
+
+fn is_sprite<T: 'static>() -> bool {
+    std::any::TypeId::of::<T>() == std::any::TypeId::of::<crate::Sprite>()
+}
+
+fn ser<T: Serialize>(value: &T, sprite: bool) -> String {
     let mut writer = Vec::with_capacity(128);
     let formatter = Formatter {
-        channel_state: if std::any::TypeId::of::<T>() == std::any::TypeId::of::<crate::Sprite>() {
+        channel_state: if sprite {
             ChannelState::Possible
         } else {
             ChannelState::Never
@@ -59,10 +79,10 @@ fn alphabetize_value(value: serde_json::Value) -> serde_json::Value {
                     new_output.insert(new_key, String::new().into());
                 }
 
-                if let Some(name) = name {
-                    if !matches!(ty, "GMSpriteFramesTrack") {
-                        new_output.insert("%Name".into(), name.as_str().unwrap().into());
-                    }
+                if let Some(name) = name
+                    && !matches!(ty, "GMSpriteFramesTrack")
+                {
+                    new_output.insert("%Name".into(), name.as_str().unwrap().into());
                 }
             }
 
@@ -384,6 +404,16 @@ mod tests {
         assert_eq!(x, o);
     }
 
+    #[test]
+    fn untyped_sprite_serialization() {
+        let x = include_str!("./../data/formatting/sprite_zero.yy");
+        let json: Value =
+            serde_json::from_str(&crate::TrailingCommaUtility::clear_trailing_comma_once(x))
+                .unwrap();
+
+        pretty_assertions::assert_eq!(x, serialize_untyped_file(&json));
+    }
+
     #[test]
     fn sprite_serialization2() {
         let x = include_str!("./../data/formatting/floater.yy");
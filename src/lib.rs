@@ -150,8 +150,8 @@ pub use shader::*;
 mod sound;
 pub use sound::*;
 
-// pub mod room;
-// pub use room::*;
+mod room;
+pub use room::*;
 
 mod resource_version;
 pub use resource_version::ResourceVersion;
@@ -159,6 +159,12 @@ pub use resource_version::ResourceVersion;
 mod yyp;
 pub use yyp::*;
 
+mod reference_graph;
+pub use reference_graph::*;
+
+mod project;
+pub use project::*;
+
 mod unidentified_resource;
 pub use unidentified_resource::*;
 
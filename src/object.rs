@@ -1,9 +1,39 @@
 mod event_type;
 pub use event_type::*;
 
+mod event_key;
+pub use event_key::*;
+
+mod event_order;
+pub use event_order::*;
+
+mod event_files;
+pub use event_files::*;
+
+mod event_list;
+pub use event_list::*;
+
+mod hierarchy;
+pub use hierarchy::*;
+
+mod resolve;
+pub use resolve::*;
+
+mod property_value;
+pub use property_value::*;
+
+mod physics;
+pub use physics::*;
+
 mod vk;
 pub use vk::*;
 
+mod gml;
+pub use gml::*;
+
+mod builder;
+pub use builder::*;
+
 use crate::{FilesystemPath, VersionStamp};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
@@ -0,0 +1,297 @@
+use super::{
+    EventKey, Object, ObjectEvent, ObjectProperty, PhysicsShape, PhysicsVec2, ResolvedObject,
+};
+use crate::{FilesystemPath, Sprite, ViewPath};
+use std::{collections::BTreeSet, path::Path};
+
+/// Builds a new [`Object`], along with the GML files for its events.
+///
+/// The builder starts from the values the IDE gives a new Object, so only
+/// what differs needs to be set:
+///
+/// ```
+/// # use yy_typings::{EventType, ObjectBuilder, Stage, ViewPath, ViewPathLocation};
+/// let folder = ViewPath {
+///     name: "Objects".to_string(),
+///     path: ViewPathLocation("folders/Objects.yy".to_string()),
+/// };
+/// let object = ObjectBuilder::new("obj_player", folder)
+///     .sprite("spr_player")
+///     .event(EventType::Create)
+///     .event_with_code(EventType::Step(Stage::Main), "x += 1;\n")
+///     .build();
+///
+/// assert_eq!(object.event_list.len(), 2);
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct ObjectBuilder {
+    object: Object,
+    code: Vec<String>,
+    parent_events: BTreeSet<EventKey>,
+}
+
+impl ObjectBuilder {
+    /// Starts a new Object with the given name, placed in `folder` in the
+    /// IDE's asset browser.
+    pub fn new(name: &str, folder: ViewPath) -> Self {
+        Self {
+            object: Object {
+                common_data: crate::CommonData::new(name.to_string()),
+                managed: true,
+                parent: folder,
+                physics_angular_damping: 0.1,
+                physics_density: 0.5,
+                physics_friction: 0.2,
+                physics_linear_damping: 0.1,
+                physics_restitution: 0.1,
+                physics_start_awake: true,
+                visible: true,
+                ..Default::default()
+            },
+            code: vec![],
+            parent_events: BTreeSet::new(),
+        }
+    }
+
+    /// Makes the Object inherit from `parent`. Events which `parent` defines
+    /// will call `event_inherited` in their stubs.
+    ///
+    /// Only the events of `parent` itself are known here; to include the
+    /// events `parent` inherits, use [`resolved_parent`](Self::resolved_parent).
+    pub fn parent(mut self, parent: &Object) -> Self {
+        self.object.parent_object_id =
+            Some(FilesystemPath::new("objects", &parent.common_data.name));
+        self.parent_events = parent.event_list.iter().map(ObjectEvent::key).collect();
+
+        self
+    }
+
+    /// Makes the Object inherit from the first Object in `parent`'s chain.
+    /// Every event which `parent` runs, including those it inherits, will
+    /// call `event_inherited` in their stubs.
+    pub fn resolved_parent(mut self, parent: &ResolvedObject<'_>) -> Self {
+        self.object.parent_object_id = Some(FilesystemPath::new(
+            "objects",
+            &parent.chain[0].common_data.name,
+        ));
+        self.parent_events = parent
+            .events
+            .iter()
+            .map(|event| event.event.key())
+            .collect();
+
+        self
+    }
+
+    /// Sets the Sprite of the Object.
+    pub fn sprite(mut self, name: &str) -> Self {
+        self.object.sprite_id = Some(FilesystemPath::new("sprites", name));
+
+        self
+    }
+
+    /// Sets the Sprite used as the Object's collision mask.
+    pub fn sprite_mask(mut self, name: &str) -> Self {
+        self.object.sprite_mask_id = Some(FilesystemPath::new("sprites", name));
+
+        self
+    }
+
+    /// Makes the Object a physics object with the given shape. See
+    /// [`Object::physics_geometry`] for how `points` are read.
+    pub fn physics(mut self, shape: PhysicsShape, points: Vec<PhysicsVec2>) -> Self {
+        self.object.physics_object = true;
+        self.object.physics_shape = shape;
+        self.object.physics_shape_points = points;
+
+        self
+    }
+
+    /// Makes the Object a physics object with a shape of the given kind,
+    /// fitted to `sprite` as in [`Object::set_default_physics_shape`].
+    pub fn physics_from_sprite(mut self, shape: PhysicsShape, sprite: &Sprite) -> Self {
+        self.object.physics_object = true;
+        self.object.set_default_physics_shape(shape, sprite);
+
+        self
+    }
+
+    /// Sets whether the Object is solid.
+    pub fn solid(mut self, solid: bool) -> Self {
+        self.object.solid = solid;
+
+        self
+    }
+
+    /// Sets whether the Object is visible.
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.object.visible = visible;
+
+        self
+    }
+
+    /// Sets whether the Object is persistent.
+    pub fn persistent(mut self, persistent: bool) -> Self {
+        self.object.persistent = persistent;
+
+        self
+    }
+
+    /// Adds a tag to the Object.
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.object.tags.push(tag.to_string());
+
+        self
+    }
+
+    /// Adds a property to the Object.
+    pub fn property(mut self, property: ObjectProperty) -> Self {
+        self.object.properties.push(property);
+
+        self
+    }
+
+    /// Adds an event with an empty stub. See
+    /// [`event_with_code`](Self::event_with_code).
+    pub fn event(self, key: impl Into<EventKey>) -> Self {
+        self.event_with_code(key, "")
+    }
+
+    /// Adds an event whose GML file holds `code`. If the parent defines the
+    /// same event, `event_inherited();` is written before `code`.
+    ///
+    /// Adding an event which the Object already has replaces its code.
+    pub fn event_with_code(mut self, key: impl Into<EventKey>, code: &str) -> Self {
+        let key = key.into();
+
+        match self
+            .object
+            .event_list
+            .iter()
+            .position(|event| event.key() == key)
+        {
+            Some(index) => self.code[index] = code.to_string(),
+            None => {
+                self.object.event_list.push(ObjectEvent::new(key));
+                self.code.push(code.to_string());
+            }
+        }
+
+        self
+    }
+
+    /// Returns the name and contents of each event's GML file, in the order
+    /// the events were added. Names do not include the `.gml` extension.
+    pub fn event_files(&self) -> Vec<(String, String)> {
+        self.object
+            .event_list
+            .iter()
+            .zip(self.code.iter())
+            .map(|(event, code)| {
+                let contents = if self.parent_events.contains(&event.key()) {
+                    format!("event_inherited();\n{}", code)
+                } else {
+                    code.clone()
+                };
+
+                (event.filename(), contents)
+            })
+            .collect()
+    }
+
+    /// Finishes the Object, without writing any files.
+    pub fn build(self) -> Object {
+        self.object
+    }
+
+    /// Writes the Object's `yy` file and the GML files for its events into a
+    /// new directory named after the Object within `objects_dir`, which is
+    /// usually the project's `objects` directory. Returns the Object, which
+    /// still needs adding to the project's `yyp`.
+    ///
+    /// # Errors
+    ///
+    /// Errors if any of the files cannot be written.
+    pub fn write(self, objects_dir: &Path) -> std::io::Result<Object> {
+        let name = &self.object.common_data.name;
+        let dir = objects_dir.join(name);
+        std::fs::create_dir_all(&dir)?;
+
+        std::fs::write(
+            dir.join(format!("{}.yy", name)),
+            crate::serialize_file(&self.object),
+        )?;
+        for (filename, contents) in self.event_files() {
+            std::fs::write(dir.join(format!("{}.gml", filename)), contents)?;
+        }
+
+        Ok(self.object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventType, Stage, TrailingCommaUtility, ViewPathLocation};
+    use pretty_assertions::assert_eq;
+
+    fn folder() -> ViewPath {
+        ViewPath {
+            name: "Objects".to_string(),
+            path: ViewPathLocation("folders/Objects.yy".to_string()),
+        }
+    }
+
+    #[test]
+    fn builder() {
+        let parent = ObjectBuilder::new("par_enemy", folder())
+            .event(EventType::Create)
+            .event(EventKey::collision("obj_wall"))
+            .build();
+
+        let builder = ObjectBuilder::new("obj_bat", folder())
+            .parent(&parent)
+            .sprite("spr_bat")
+            .event_with_code(EventType::Create, "hp = 3;\n")
+            .event(EventType::Step(Stage::Main))
+            .event(EventKey::collision("obj_wall"))
+            .event_with_code(EventType::Step(Stage::Main), "x += 1;\n");
+
+        assert_eq!(
+            builder.event_files(),
+            vec![
+                (
+                    "Create_0".to_string(),
+                    "event_inherited();\nhp = 3;\n".to_string()
+                ),
+                ("Step_0".to_string(), "x += 1;\n".to_string()),
+                (
+                    "Collision_obj_wall".to_string(),
+                    "event_inherited();\n".to_string()
+                ),
+            ]
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let object = builder.write(dir.path()).unwrap();
+        assert_eq!(
+            object.parent_object_id,
+            Some(FilesystemPath::new("objects", "par_enemy"))
+        );
+        assert_eq!(
+            object.sprite_id,
+            Some(FilesystemPath::new("sprites", "spr_bat"))
+        );
+
+        let object_dir = dir.path().join("obj_bat");
+        let yy = std::fs::read_to_string(object_dir.join("obj_bat.yy")).unwrap();
+        let read: Object =
+            serde_json::from_str(&TrailingCommaUtility::clear_trailing_comma_once(&yy)).unwrap();
+        assert_eq!(read, object);
+        assert!(object.check_event_files(&object_dir).unwrap().is_empty());
+        assert_eq!(
+            std::fs::read_to_string(object_dir.join("Step_0.gml")).unwrap(),
+            "x += 1;\n"
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use super::{Object, ObjectEvent};
+use std::path::{Path, PathBuf};
+
+/// The result of [`Object::check_event_files`].
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct EventFileReport<'a> {
+    /// Events whose GML file does not exist in the Object's directory.
+    pub missing: Vec<&'a ObjectEvent>,
+
+    /// GML files in the Object's directory which belong to no event, sorted
+    /// by name. The IDE ignores these, so any code in them is never run.
+    pub orphans: Vec<PathBuf>,
+}
+
+impl EventFileReport<'_> {
+    /// Returns `true` if every event has a file and every file has an event.
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.orphans.is_empty()
+    }
+}
+
+impl ObjectEvent {
+    /// Returns the name of the event's GML file, without its extension.
+    ///
+    /// Collision events are named after the object they collide with, such as
+    /// `Collision_obj_wall`; see
+    /// [`EventKey::filename`](super::EventKey::filename). A collision event
+    /// without a `collision_object_id` falls back to `EventType::filename`.
+    pub fn filename(&self) -> String {
+        self.key().filename()
+    }
+}
+
+impl Object {
+    /// Pairs each event in `event_list` with the path of its GML file within
+    /// `object_dir`, in the order of `event_list`. The files are not checked;
+    /// see [`check_event_files`](Self::check_event_files).
+    pub fn event_files(&self, object_dir: &Path) -> Vec<(&ObjectEvent, PathBuf)> {
+        self.event_list
+            .iter()
+            .map(|event| (event, object_dir.join(format!("{}.gml", event.filename()))))
+            .collect()
+    }
+
+    /// Compares the Object's events with the GML files in `object_dir`,
+    /// reporting events which have no file and files which have no event.
+    ///
+    /// # Errors
+    ///
+    /// Errors if `object_dir` cannot be read.
+    pub fn check_event_files(&self, object_dir: &Path) -> std::io::Result<EventFileReport<'_>> {
+        let expected = self.event_files(object_dir);
+
+        let mut orphans = vec![];
+        for entry in std::fs::read_dir(object_dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "gml")
+                && path.is_file()
+                && !expected.iter().any(|(_, expected)| *expected == path)
+            {
+                orphans.push(path);
+            }
+        }
+        orphans.sort();
+
+        let missing = expected
+            .into_iter()
+            .filter(|(_, path)| !path.is_file())
+            .map(|(event, _)| event)
+            .collect();
+
+        Ok(EventFileReport { missing, orphans })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        FilesystemPath,
+        object::{EventType, Stage},
+    };
+    use pretty_assertions::assert_eq;
+
+    fn event(event_type: EventType, collision_object: Option<&str>) -> ObjectEvent {
+        ObjectEvent {
+            event_type,
+            collision_object_id: collision_object.map(|name| FilesystemPath::new("objects", name)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn filenames() {
+        assert_eq!(event(EventType::Create, None).filename(), "Create_0");
+        assert_eq!(
+            event(EventType::Step(Stage::End), None).filename(),
+            "Step_2"
+        );
+        assert_eq!(
+            event(EventType::Collision, Some("obj_wall")).filename(),
+            "Collision_obj_wall"
+        );
+    }
+
+    #[test]
+    fn check_event_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let object = Object {
+            event_list: vec![
+                event(EventType::Create, None),
+                event(EventType::Step(Stage::Main), None),
+                event(EventType::Collision, Some("obj_wall")),
+            ],
+            ..Default::default()
+        };
+
+        for file in ["Create_0.gml", "Collision_obj_wall.gml", "Step_1.gml"] {
+            std::fs::write(dir.path().join(file), "").unwrap();
+        }
+        std::fs::write(dir.path().join("obj_test.yy"), "").unwrap();
+
+        let files = object.event_files(dir.path());
+        assert_eq!(files[2].1, dir.path().join("Collision_obj_wall.gml"));
+
+        let report = object.check_event_files(dir.path()).unwrap();
+        assert_eq!(report.missing, vec![&object.event_list[1]]);
+        assert_eq!(report.orphans, vec![dir.path().join("Step_1.gml")]);
+        assert!(!report.is_empty());
+    }
+}
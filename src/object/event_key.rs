@@ -0,0 +1,213 @@
+use super::{EventType, EventTypeConvertErrors, ObjectEvent};
+use crate::FilesystemPath;
+use std::fmt;
+
+/// Identifies an event of an Object, including the object which a collision
+/// event collides with.
+///
+/// `EventType::Collision` has no payload, since the target of a collision
+/// event is stored in `ObjectEvent::collision_object_id` instead. Two collision
+/// events with different targets therefore have the same `EventType`, but
+/// different `EventKey`s, which makes this the type to use as a map key.
+///
+/// Keys are built with [`EventKey::collision`] or from an [`EventType`], so
+/// only a collision event can have a target.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Hash, Default, Clone)]
+pub struct EventKey {
+    event_type: EventType,
+    collision_object: Option<String>,
+}
+
+impl EventKey {
+    /// Creates a key for a collision event with the given object.
+    pub fn collision(object: impl Into<String>) -> Self {
+        Self {
+            event_type: EventType::Collision,
+            collision_object: Some(object.into()),
+        }
+    }
+
+    /// The type of the event.
+    pub fn event_type(&self) -> EventType {
+        self.event_type
+    }
+
+    /// The name of the object which a collision event collides with. This is
+    /// always `None` for any other event.
+    pub fn collision_object(&self) -> Option<&str> {
+        self.collision_object.as_deref()
+    }
+
+    /// Returns the filename like it will appear in a file, without its
+    /// extension. Collision events are named after their target, such as
+    /// `Collision_obj_wall`.
+    pub fn filename(&self) -> String {
+        match &self.collision_object {
+            Some(object) => format!("Collision_{}", object),
+            None => self.event_type.filename(),
+        }
+    }
+
+    /// Parses a filename, without its extension, into an `EventKey`. This is
+    /// the reverse of [`filename`](Self::filename).
+    ///
+    /// # Errors
+    ///
+    /// Errors if the filename does not follow the pattern `x_y`, if it is a
+    /// collision event whose target is not an object name, or if it cannot be
+    /// parsed by [`EventType::parse_filename`].
+    pub fn parse_filename(value: &str) -> Result<Self, EventTypeConvertErrors> {
+        let (name, event_num) = value
+            .split_once('_')
+            .ok_or(EventTypeConvertErrors::BadString)?;
+
+        if name == "Collision" {
+            return Self::collision_target(event_num);
+        }
+
+        let event_num = event_num
+            .parse()
+            .map_err(|_| EventTypeConvertErrors::BadString)?;
+
+        EventType::parse_filename(name, event_num).map(Self::from)
+    }
+
+    /// Converts to a heuristic filename, suitable for humans. Collision
+    /// events become `collision_` followed by their target.
+    pub fn to_human_readable(&self) -> String {
+        match &self.collision_object {
+            Some(object) => format!("collision_{}", object),
+            None => self.event_type.to_human_readable(),
+        }
+    }
+
+    /// Parses the output of [`to_human_readable`](Self::to_human_readable).
+    /// Anything which is not a collision event is parsed with
+    /// [`EventType::from_human_readable`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if `value` is a collision event whose target is not an object
+    /// name, or if it cannot be parsed by [`EventType::from_human_readable`].
+    pub fn from_human_readable(value: &str) -> Result<Self, EventTypeConvertErrors> {
+        match value
+            .strip_prefix("collision_")
+            .or_else(|| value.strip_prefix("Collision_"))
+        {
+            Some(object) => Self::collision_target(object),
+            None => EventType::from_human_readable(value).map(Self::from),
+        }
+    }
+
+    /// Creates a collision key for a target parsed out of a name. Object names
+    /// cannot be empty or start with a digit, so a suffix such as the `0` in
+    /// `Collision_0` is rejected rather than taken as an object.
+    fn collision_target(object: &str) -> Result<Self, EventTypeConvertErrors> {
+        if object.is_empty() || object.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(EventTypeConvertErrors::BadString);
+        }
+
+        Ok(Self::collision(object))
+    }
+}
+
+impl From<EventType> for EventKey {
+    fn from(event_type: EventType) -> Self {
+        Self {
+            event_type,
+            collision_object: None,
+        }
+    }
+}
+
+impl fmt::Display for EventKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.collision_object {
+            Some(object) => write!(f, "Collision - {}", object),
+            None => write!(f, "{}", self.event_type),
+        }
+    }
+}
+
+impl ObjectEvent {
+    /// Creates a new event for the given key. The target of a collision event
+    /// is assumed to live in the `objects` folder, where the IDE puts it.
+    pub fn new(key: EventKey) -> Self {
+        Self {
+            collision_object_id: key
+                .collision_object
+                .map(|object| FilesystemPath::new("objects", &object)),
+            event_type: key.event_type,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the key of the event, which combines its `event_type` with the
+    /// target of a collision event.
+    pub fn key(&self) -> EventKey {
+        let collision_object = match self.event_type {
+            EventType::Collision => self.collision_object_id.as_ref().map(|id| id.name.clone()),
+            _ => None,
+        };
+
+        EventKey {
+            event_type: self.event_type,
+            collision_object,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::{OtherEvent, Stage};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn filename_symmetry() {
+        let keys = [
+            EventKey::from(EventType::Create),
+            EventKey::from(EventType::Step(Stage::Begin)),
+            EventKey::from(EventType::Other(OtherEvent::RoomStart)),
+            EventKey::collision("obj_wall"),
+            EventKey::collision("obj_some_long_name"),
+        ];
+
+        for key in keys {
+            assert_eq!(EventKey::parse_filename(&key.filename()).unwrap(), key);
+            assert_eq!(
+                EventKey::from_human_readable(&key.to_human_readable()).unwrap(),
+                key
+            );
+        }
+
+        assert_eq!(
+            EventKey::collision("obj_wall").filename(),
+            "Collision_obj_wall"
+        );
+        assert!(EventKey::parse_filename("Create").is_err());
+        assert!(EventKey::parse_filename("Collision_0").is_err());
+        assert!(EventKey::parse_filename("Collision_").is_err());
+        assert!(EventKey::from_human_readable("collision_12").is_err());
+    }
+
+    #[test]
+    fn collision_keys() {
+        let wall = ObjectEvent::new(EventKey::collision("obj_wall"));
+        let door = ObjectEvent::new(EventKey::collision("obj_door"));
+        assert_eq!(wall.event_type, door.event_type);
+        assert_ne!(wall.key(), door.key());
+
+        assert_eq!(
+            wall.collision_object_id,
+            Some(FilesystemPath::new("objects", "obj_wall"))
+        );
+        assert_eq!(wall.key().to_string(), "Collision - obj_wall");
+        assert_eq!(wall.key().collision_object(), Some("obj_wall"));
+        assert_eq!(EventKey::from(EventType::Create).collision_object(), None);
+
+        let json = serde_json::to_value(&wall).unwrap();
+        assert_eq!(json["eventType"], 4);
+        assert_eq!(json["collisionObjectId"]["name"], "obj_wall");
+    }
+}
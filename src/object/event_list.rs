@@ -0,0 +1,163 @@
+use super::{EventIntermediary, EventKey, EventType, Object};
+
+/// Problems in an Object's `event_list`. See [`Object::validate`].
+#[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+pub enum EventListError {
+    #[error("event `{key}` appears more than once, at {first} and {duplicate}")]
+    Duplicate {
+        key: EventKey,
+        first: usize,
+        duplicate: usize,
+    },
+    #[error("event `{event}` at {index} is out of range")]
+    Invalid { event: EventType, index: usize },
+    #[error("collision event at {0} does not say which object it collides with")]
+    MissingCollisionObject(usize),
+}
+
+impl Object {
+    /// Checks the Object's `event_list` for events which the IDE would
+    /// reject: the same event appearing twice, events which are out of
+    /// range, such as an alarm above [`EventType::ALARM_MAX`], and collision
+    /// events without a `collision_object_id`. Events are referred to by
+    /// their index in `event_list`.
+    ///
+    /// Deserializing only produces valid event types, so `Invalid` errors
+    /// come from events made in code.
+    ///
+    /// # Errors
+    ///
+    /// Errors with every problem found, in the order of `event_list`.
+    pub fn validate(&self) -> Result<(), Vec<EventListError>> {
+        let mut errors = vec![];
+
+        for (index, event) in self.event_list.iter().enumerate() {
+            if !EventType::is_valid(event.event_type) {
+                errors.push(EventListError::Invalid {
+                    event: event.event_type,
+                    index,
+                });
+            }
+
+            if event.event_type == EventType::Collision && event.collision_object_id.is_none() {
+                errors.push(EventListError::MissingCollisionObject(index));
+            }
+
+            let key = event.key();
+            if let Some(first) = self.event_list[..index]
+                .iter()
+                .position(|other| other.key() == key)
+            {
+                errors.push(EventListError::Duplicate {
+                    key,
+                    first,
+                    duplicate: index,
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Sorts `event_list` into a canonical order: by event type number, then
+    /// by event number, with collision events ordered by the name of the
+    /// object they collide with. This is not the order the IDE uses, which
+    /// keeps events in the order they were added, and it is not the order
+    /// events run in; see [`EventType::cmp_runtime_order`] for that.
+    ///
+    /// The sort is stable, so duplicate events keep their relative order.
+    pub fn sort_events(&mut self) {
+        self.event_list.sort_by_cached_key(|event| {
+            (
+                EventIntermediary::from(event.event_type),
+                event.collision_object_id.as_ref().map(|id| id.name.clone()),
+            )
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::{DrawEvent, ObjectEvent, OtherEvent, Stage};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn validate() {
+        let mut unnamed_collision = ObjectEvent::new(EventType::Collision.into());
+        unnamed_collision.collision_object_id = None;
+
+        let object = Object {
+            event_list: vec![
+                ObjectEvent::new(EventType::Create.into()),
+                ObjectEvent::new(EventKey::collision("obj_wall")),
+                ObjectEvent::new(EventType::Alarm(EventType::ALARM_MAX + 1).into()),
+                ObjectEvent::new(EventKey::collision("obj_floor")),
+                ObjectEvent::new(EventType::Create.into()),
+                unnamed_collision,
+                ObjectEvent::new(EventKey::collision("obj_wall")),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            object.validate().unwrap_err(),
+            vec![
+                EventListError::Invalid {
+                    event: EventType::Alarm(EventType::ALARM_MAX + 1),
+                    index: 2
+                },
+                EventListError::Duplicate {
+                    key: EventType::Create.into(),
+                    first: 0,
+                    duplicate: 4
+                },
+                EventListError::MissingCollisionObject(5),
+                EventListError::Duplicate {
+                    key: EventKey::collision("obj_wall"),
+                    first: 1,
+                    duplicate: 6
+                },
+            ]
+        );
+
+        assert_eq!(Object::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn sort_events() {
+        let mut object = Object {
+            event_list: vec![
+                ObjectEvent::new(EventType::Draw(DrawEvent::Draw(Stage::Main)).into()),
+                ObjectEvent::new(EventKey::collision("obj_wall")),
+                ObjectEvent::new(EventType::Other(OtherEvent::UserEvent(0)).into()),
+                ObjectEvent::new(EventType::Step(Stage::End).into()),
+                ObjectEvent::new(EventType::CleanUp.into()),
+                ObjectEvent::new(EventKey::collision("obj_floor")),
+                ObjectEvent::new(EventType::Step(Stage::Main).into()),
+                ObjectEvent::new(EventType::Create.into()),
+            ],
+            ..Default::default()
+        };
+        object.sort_events();
+
+        let keys: Vec<_> = object.event_list.iter().map(ObjectEvent::key).collect();
+        assert_eq!(
+            keys,
+            vec![
+                EventType::Create.into(),
+                EventType::Step(Stage::Main).into(),
+                EventType::Step(Stage::End).into(),
+                EventKey::collision("obj_floor"),
+                EventKey::collision("obj_wall"),
+                EventType::Other(OtherEvent::UserEvent(0)).into(),
+                EventType::Draw(DrawEvent::Draw(Stage::Main)).into(),
+                EventType::CleanUp.into(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use super::{DrawEvent, EventIntermediary, EventType, Object, ObjectEvent, OtherEvent, Stage};
+use std::cmp::Ordering;
+
+/// The phases of a GameMaker frame, in the order the runtime runs them.
+///
+/// The first three phases only run when an instance is made, or a room or the
+/// game starts, and the last five only run when an instance is destroyed, a
+/// room or the game ends, or when GML calls `event_user`.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub enum RuntimePhase {
+    Create,
+    GameStart,
+    RoomStart,
+    BeginStep,
+    Alarm,
+    Keyboard,
+    Mouse,
+    Step,
+    /// Outside and intersect events for the room and views, which are checked
+    /// after instances move.
+    Boundary,
+    Collision,
+    PathEnded,
+    EndStep,
+    /// Animation and broadcast events, which fire as instances advance their
+    /// sprites at the end of the step.
+    Animation,
+    /// Asynchronous events, which are dispatched once per frame, between the
+    /// step and draw events.
+    Async,
+    PreDraw,
+    DrawBegin,
+    Draw,
+    DrawEnd,
+    PostDraw,
+    DrawGuiBegin,
+    DrawGui,
+    DrawGuiEnd,
+    WindowResize,
+    RoomEnd,
+    GameEnd,
+    Destroy,
+    CleanUp,
+    UserEvent,
+}
+
+impl EventType {
+    /// Returns a key which sorts events in the order the GameMaker runtime
+    /// fires them within a frame, rather than the declaration order which the
+    /// derived `Ord` uses.
+    ///
+    /// Events in the same phase are ordered by their event type and number,
+    /// so alarms run in order, key down events run before key press events,
+    /// and so on.
+    pub fn runtime_order(&self) -> (RuntimePhase, EventIntermediary) {
+        let phase = match self {
+            EventType::Create => RuntimePhase::Create,
+            EventType::Step(Stage::Begin) => RuntimePhase::BeginStep,
+            EventType::Step(Stage::Main) => RuntimePhase::Step,
+            EventType::Step(Stage::End) => RuntimePhase::EndStep,
+            EventType::Alarm(_) => RuntimePhase::Alarm,
+            EventType::KeyDown(_) | EventType::KeyPress(_) | EventType::KeyRelease(_) => {
+                RuntimePhase::Keyboard
+            }
+            EventType::Mouse(_) | EventType::Gesture(_) => RuntimePhase::Mouse,
+            EventType::Collision => RuntimePhase::Collision,
+            EventType::Draw(draw_event) => match draw_event {
+                DrawEvent::PreDraw => RuntimePhase::PreDraw,
+                DrawEvent::Draw(Stage::Begin) => RuntimePhase::DrawBegin,
+                DrawEvent::Draw(Stage::Main) => RuntimePhase::Draw,
+                DrawEvent::Draw(Stage::End) => RuntimePhase::DrawEnd,
+                DrawEvent::PostDraw => RuntimePhase::PostDraw,
+                DrawEvent::DrawGui(Stage::Begin) => RuntimePhase::DrawGuiBegin,
+                DrawEvent::DrawGui(Stage::Main) => RuntimePhase::DrawGui,
+                DrawEvent::DrawGui(Stage::End) => RuntimePhase::DrawGuiEnd,
+                DrawEvent::WindowResize => RuntimePhase::WindowResize,
+            },
+            EventType::Other(other) => match other {
+                OtherEvent::GameStart => RuntimePhase::GameStart,
+                OtherEvent::RoomStart => RuntimePhase::RoomStart,
+                OtherEvent::OutsideRoom
+                | OtherEvent::IntersectBoundary
+                | OtherEvent::OutsideView(_)
+                | OtherEvent::IntersectView(_) => RuntimePhase::Boundary,
+                OtherEvent::PathEnded => RuntimePhase::PathEnded,
+                OtherEvent::AnimationEnd
+                | OtherEvent::AnimationUpdate
+                | OtherEvent::AnimationEvent
+                | OtherEvent::BroadcastMessage => RuntimePhase::Animation,
+                OtherEvent::RoomEnd => RuntimePhase::RoomEnd,
+                OtherEvent::GameEnd => RuntimePhase::GameEnd,
+                OtherEvent::UserEvent(_) => RuntimePhase::UserEvent,
+            },
+            EventType::Async(_) => RuntimePhase::Async,
+            EventType::Destroy => RuntimePhase::Destroy,
+            EventType::CleanUp => RuntimePhase::CleanUp,
+        };
+
+        (phase, EventIntermediary::from(*self))
+    }
+
+    /// Compares two events by the order the GameMaker runtime fires them.
+    /// See [`runtime_order`](Self::runtime_order).
+    pub fn cmp_runtime_order(&self, other: &EventType) -> Ordering {
+        self.runtime_order().cmp(&other.runtime_order())
+    }
+}
+
+impl Object {
+    /// Returns the Object's events in the order the GameMaker runtime fires
+    /// them. Collision events keep their order from `event_list`.
+    pub fn events_in_runtime_order(&self) -> Vec<&ObjectEvent> {
+        let mut events: Vec<_> = self.event_list.iter().collect();
+        events.sort_by(|a, b| a.event_type.cmp_runtime_order(&b.event_type));
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::{MouseButton, MouseEvent, VirtualKeyCode};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn runtime_order() {
+        let expected = vec![
+            EventType::Create,
+            EventType::Other(OtherEvent::RoomStart),
+            EventType::Step(Stage::Begin),
+            EventType::Alarm(0),
+            EventType::Alarm(3),
+            EventType::KeyDown(VirtualKeyCode::Left),
+            EventType::KeyPress(VirtualKeyCode::Space),
+            EventType::Mouse(MouseEvent::Pressed(MouseButton::default())),
+            EventType::Step(Stage::Main),
+            EventType::Other(OtherEvent::OutsideRoom),
+            EventType::Collision,
+            EventType::Step(Stage::End),
+            EventType::Other(OtherEvent::AnimationEnd),
+            EventType::Draw(DrawEvent::Draw(Stage::Begin)),
+            EventType::Draw(DrawEvent::Draw(Stage::Main)),
+            EventType::Draw(DrawEvent::DrawGui(Stage::Main)),
+            EventType::Destroy,
+            EventType::CleanUp,
+        ];
+
+        let mut events = expected.clone();
+        events.reverse();
+        events.sort_by(EventType::cmp_runtime_order);
+        assert_eq!(events, expected);
+
+        let object = Object {
+            event_list: vec![
+                ObjectEvent::new(EventType::Draw(DrawEvent::Draw(Stage::Main)).into()),
+                ObjectEvent::new(EventType::Step(Stage::Main).into()),
+                ObjectEvent::new(EventType::Step(Stage::Begin).into()),
+            ],
+            ..Default::default()
+        };
+        let events: Vec<_> = object
+            .events_in_runtime_order()
+            .into_iter()
+            .map(|event| event.event_type)
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                EventType::Step(Stage::Begin),
+                EventType::Step(Stage::Main),
+                EventType::Draw(DrawEvent::Draw(Stage::Main))
+            ]
+        );
+    }
+}
@@ -22,6 +22,11 @@ pub enum EventType {
     Draw(DrawEvent),
 
     Alarm(usize),
+    /// The object collided with is stored separately, in
+    /// `ObjectEvent::collision_object_id`. Use [`EventKey`] to tell collision
+    /// events apart.
+    ///
+    /// [`EventKey`]: super::EventKey
     Collision,
 
     Mouse(MouseEvent),
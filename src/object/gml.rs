@@ -0,0 +1,661 @@
+use super::{
+    AsyncEvent, DrawEvent, EventIntermediary, EventKey, EventType, EventTypeConvertErrors,
+    EventTypeNumber, Gesture, GestureEvent, MouseButtonCode, MouseEvent, OtherEvent, Stage,
+    UnknownEventTypeNumber, VirtualKeyCode,
+};
+use num_traits::FromPrimitive;
+use std::fmt;
+
+/// An event as GML refers to it, such as in `event_perform`: the constant for
+/// its event type, and an expression for its event number.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct GmlEvent {
+    /// The event type constant, such as `ev_step`.
+    pub event_type: &'static str,
+
+    /// The event number, such as `ev_step_begin`, `vk_left`, `ord("A")`, or
+    /// an alarm number. For collision events, this is the target object.
+    pub event_number: String,
+}
+
+impl fmt::Display for GmlEvent {
+    /// Writes the event as the arguments of `event_perform`, such as
+    /// `ev_step, ev_step_begin`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}", self.event_type, self.event_number)
+    }
+}
+
+impl EventTypeNumber {
+    const ALL: [EventTypeNumber; 13] = [
+        EventTypeNumber::Create,
+        EventTypeNumber::Destroy,
+        EventTypeNumber::Alarm,
+        EventTypeNumber::Step,
+        EventTypeNumber::Collision,
+        EventTypeNumber::Keyboard,
+        EventTypeNumber::Mouse,
+        EventTypeNumber::Other,
+        EventTypeNumber::Draw,
+        EventTypeNumber::KeyPress,
+        EventTypeNumber::KeyRelease,
+        EventTypeNumber::CleanUp,
+        EventTypeNumber::Gesture,
+    ];
+
+    /// The GML constant for this event type, such as `ev_step`.
+    pub fn gml_constant(&self) -> &'static str {
+        match self {
+            EventTypeNumber::Create => "ev_create",
+            EventTypeNumber::Destroy => "ev_destroy",
+            EventTypeNumber::Alarm => "ev_alarm",
+            EventTypeNumber::Step => "ev_step",
+            EventTypeNumber::Collision => "ev_collision",
+            EventTypeNumber::Keyboard => "ev_keyboard",
+            EventTypeNumber::Mouse => "ev_mouse",
+            EventTypeNumber::Other => "ev_other",
+            EventTypeNumber::Draw => "ev_draw",
+            EventTypeNumber::KeyPress => "ev_keypress",
+            EventTypeNumber::KeyRelease => "ev_keyrelease",
+            EventTypeNumber::CleanUp => "ev_cleanup",
+            EventTypeNumber::Gesture => "ev_gesture",
+        }
+    }
+
+    /// Parses a GML event type constant, such as `ev_step`.
+    pub fn from_gml_constant(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|event_type| event_type.gml_constant() == value.trim())
+    }
+}
+
+impl EventType {
+    /// Converts the event into the GML constants which refer to it, as used
+    /// by `event_perform`.
+    ///
+    /// Returns `None` for collision events, which need the object they
+    /// collide with; see [`EventKey::to_gml`].
+    pub fn to_gml(&self) -> Option<GmlEvent> {
+        let event_number = match self {
+            EventType::Create | EventType::Destroy | EventType::CleanUp => "0".to_string(),
+            EventType::Step(stage) => match stage {
+                Stage::Main => "ev_step_normal",
+                Stage::Begin => "ev_step_begin",
+                Stage::End => "ev_step_end",
+            }
+            .to_string(),
+            EventType::Alarm(alarm) => alarm.to_string(),
+            EventType::Draw(draw_event) => match draw_event {
+                DrawEvent::Draw(Stage::Main) => "ev_draw_normal",
+                DrawEvent::Draw(Stage::Begin) => "ev_draw_begin",
+                DrawEvent::Draw(Stage::End) => "ev_draw_end",
+                DrawEvent::DrawGui(Stage::Main) => "ev_gui",
+                DrawEvent::DrawGui(Stage::Begin) => "ev_gui_begin",
+                DrawEvent::DrawGui(Stage::End) => "ev_gui_end",
+                DrawEvent::PreDraw => "ev_draw_pre",
+                DrawEvent::PostDraw => "ev_draw_post",
+                // the runtime has no constant for this one.
+                DrawEvent::WindowResize => "65",
+            }
+            .to_string(),
+            EventType::Collision => return None,
+            EventType::Mouse(mouse_event) => mouse_event.gml_constant().to_string(),
+            EventType::KeyDown(vk) | EventType::KeyPress(vk) | EventType::KeyRelease(vk) => {
+                vk.to_gml()
+            }
+            EventType::Gesture(gesture) => gesture.gml_constant(),
+            EventType::Other(other) => match other {
+                OtherEvent::OutsideRoom => "ev_outside".to_string(),
+                OtherEvent::IntersectBoundary => "ev_boundary".to_string(),
+                OtherEvent::OutsideView(view) => format!("ev_outside_view{}", view),
+                OtherEvent::IntersectView(view) => format!("ev_boundary_view{}", view),
+                OtherEvent::GameStart => "ev_game_start".to_string(),
+                OtherEvent::GameEnd => "ev_game_end".to_string(),
+                OtherEvent::RoomStart => "ev_room_start".to_string(),
+                OtherEvent::RoomEnd => "ev_room_end".to_string(),
+                OtherEvent::AnimationEnd => "ev_animation_end".to_string(),
+                OtherEvent::AnimationUpdate => "ev_animation_update".to_string(),
+                OtherEvent::AnimationEvent => "ev_animation_event".to_string(),
+                OtherEvent::PathEnded => "ev_end_of_path".to_string(),
+                OtherEvent::UserEvent(event) => format!("ev_user{}", event),
+                OtherEvent::BroadcastMessage => "ev_broadcast_message".to_string(),
+            },
+            EventType::Async(async_event) => match async_event {
+                AsyncEvent::AudioPlayback => "ev_async_audio_playback",
+                AsyncEvent::AudioRecording => "ev_async_audio_recording",
+                AsyncEvent::Cloud => "ev_async_web_cloud",
+                AsyncEvent::Dialog => "ev_async_dialog",
+                AsyncEvent::Http => "ev_async_web",
+                AsyncEvent::InAppPurchase => "ev_async_web_iap",
+                AsyncEvent::ImageLoaded => "ev_async_web_image_load",
+                AsyncEvent::Networking => "ev_async_web_networking",
+                AsyncEvent::PushNotification => "ev_async_push_notification",
+                AsyncEvent::SaveLoad => "ev_async_save_load",
+                AsyncEvent::Social => "ev_async_social",
+                AsyncEvent::Steam => "ev_async_web_steam",
+                AsyncEvent::System => "ev_async_system_event",
+            }
+            .to_string(),
+        };
+
+        Some(GmlEvent {
+            event_type: EventTypeNumber::from(self).gml_constant(),
+            event_number,
+        })
+    }
+
+    /// Parses the GML constants which refer to an event, such as `ev_step`
+    /// and `ev_step_begin`. This is the reverse of [`to_gml`](Self::to_gml).
+    /// Event numbers may also be given as plain numbers.
+    ///
+    /// # Errors
+    ///
+    /// Errors if `event_type` is not an event type constant, if
+    /// `event_number` is not valid for it, or if the event is a collision
+    /// event, which needs [`EventKey::from_gml`] instead.
+    pub fn from_gml(
+        event_type: &str,
+        event_number: &str,
+    ) -> Result<EventType, EventTypeConvertErrors> {
+        let event_type =
+            EventTypeNumber::from_gml_constant(event_type).ok_or(UnknownEventTypeNumber)?;
+        let event_number = event_number.trim();
+        let not_found = EventTypeConvertErrors::CannotFindEventNumber(event_type as usize);
+
+        if let Ok(event_num) = event_number.parse() {
+            return EventType::try_from(EventIntermediary {
+                event_type: event_type as usize,
+                event_num,
+            });
+        }
+
+        match event_type {
+            EventTypeNumber::Keyboard | EventTypeNumber::KeyPress | EventTypeNumber::KeyRelease => {
+                let vk = VirtualKeyCode::from_gml(event_number).ok_or(not_found)?;
+
+                EventType::try_from(EventIntermediary {
+                    event_type: event_type as usize,
+                    event_num: vk as usize,
+                })
+            }
+            EventTypeNumber::Collision => Err(not_found),
+            _ => (0..=u8::MAX as usize)
+                .filter_map(|event_num| {
+                    EventType::try_from(EventIntermediary {
+                        event_type: event_type as usize,
+                        event_num,
+                    })
+                    .ok()
+                })
+                .find(|event| {
+                    event
+                        .to_gml()
+                        .is_some_and(|gml| gml.event_number == event_number)
+                })
+                .ok_or(not_found),
+        }
+    }
+}
+
+impl EventKey {
+    /// Converts the event into the GML constants which refer to it, as used
+    /// by `event_perform`. Collision events use the name of their target
+    /// object as their event number.
+    ///
+    /// Returns `None` for a collision event without a target.
+    pub fn to_gml(&self) -> Option<GmlEvent> {
+        match self.collision_object() {
+            Some(object) => Some(GmlEvent {
+                event_type: EventTypeNumber::Collision.gml_constant(),
+                event_number: object.to_string(),
+            }),
+            None => self.event_type().to_gml(),
+        }
+    }
+
+    /// Parses the GML constants which refer to an event. This is the reverse
+    /// of [`to_gml`](Self::to_gml).
+    ///
+    /// # Errors
+    ///
+    /// Errors if the constants cannot be parsed by [`EventType::from_gml`].
+    pub fn from_gml(event_type: &str, event_number: &str) -> Result<Self, EventTypeConvertErrors> {
+        if EventTypeNumber::from_gml_constant(event_type) == Some(EventTypeNumber::Collision) {
+            return Ok(Self::collision(event_number.trim()));
+        }
+
+        EventType::from_gml(event_type, event_number).map(Self::from)
+    }
+}
+
+impl VirtualKeyCode {
+    /// Returns the GML expression for this key, as used by `keyboard_check`.
+    /// Letters and digits have no constant, so they are written as `ord`
+    /// calls, such as `ord("A")`.
+    pub fn to_gml(&self) -> String {
+        let constant = match self {
+            VirtualKeyCode::NoKey => "vk_nokey",
+            VirtualKeyCode::AnyKey => "vk_anykey",
+            VirtualKeyCode::Backspace => "vk_backspace",
+            VirtualKeyCode::Tab => "vk_tab",
+            VirtualKeyCode::Return => "vk_enter",
+            VirtualKeyCode::Shift => "vk_shift",
+            VirtualKeyCode::Control => "vk_control",
+            VirtualKeyCode::Alt => "vk_alt",
+            VirtualKeyCode::Pause => "vk_pause",
+            VirtualKeyCode::Escape => "vk_escape",
+            VirtualKeyCode::Space => "vk_space",
+            VirtualKeyCode::PageUp => "vk_pageup",
+            VirtualKeyCode::PageDown => "vk_pagedown",
+            VirtualKeyCode::End => "vk_end",
+            VirtualKeyCode::Home => "vk_home",
+            VirtualKeyCode::Left => "vk_left",
+            VirtualKeyCode::Up => "vk_up",
+            VirtualKeyCode::Right => "vk_right",
+            VirtualKeyCode::Down => "vk_down",
+            VirtualKeyCode::Insert => "vk_insert",
+            VirtualKeyCode::Delete => "vk_delete",
+            VirtualKeyCode::Numpad0 => "vk_numpad0",
+            VirtualKeyCode::Numpad1 => "vk_numpad1",
+            VirtualKeyCode::Numpad2 => "vk_numpad2",
+            VirtualKeyCode::Numpad3 => "vk_numpad3",
+            VirtualKeyCode::Numpad4 => "vk_numpad4",
+            VirtualKeyCode::Numpad5 => "vk_numpad5",
+            VirtualKeyCode::Numpad6 => "vk_numpad6",
+            VirtualKeyCode::Numpad7 => "vk_numpad7",
+            VirtualKeyCode::Numpad8 => "vk_numpad8",
+            VirtualKeyCode::Numpad9 => "vk_numpad9",
+            VirtualKeyCode::NumpadMultiply => "vk_multiply",
+            VirtualKeyCode::NumpadAdd => "vk_add",
+            VirtualKeyCode::NumpadSubtract => "vk_subtract",
+            VirtualKeyCode::NumpadDeciminal => "vk_decimal",
+            VirtualKeyCode::NumpadDivide => "vk_divide",
+            VirtualKeyCode::F1 => "vk_f1",
+            VirtualKeyCode::F2 => "vk_f2",
+            VirtualKeyCode::F3 => "vk_f3",
+            VirtualKeyCode::F4 => "vk_f4",
+            VirtualKeyCode::F5 => "vk_f5",
+            VirtualKeyCode::F6 => "vk_f6",
+            VirtualKeyCode::F7 => "vk_f7",
+            VirtualKeyCode::F8 => "vk_f8",
+            VirtualKeyCode::F9 => "vk_f9",
+            VirtualKeyCode::F10 => "vk_f10",
+            VirtualKeyCode::F11 => "vk_f11",
+            VirtualKeyCode::F12 => "vk_f12",
+            letter_or_digit => return format!("ord(\"{}\")", *letter_or_digit as u8 as char),
+        };
+
+        constant.to_string()
+    }
+
+    /// Parses a GML expression for a key, such as `vk_left`, `ord("A")` or
+    /// `ord('a')`. This is the reverse of [`to_gml`](Self::to_gml).
+    pub fn from_gml(value: &str) -> Option<Self> {
+        let value = value.trim();
+
+        if let Some(inner) = value
+            .strip_prefix("ord(")
+            .and_then(|value| value.strip_suffix(')'))
+        {
+            let inner = inner.trim();
+            let character = inner
+                .strip_prefix('"')
+                .and_then(|inner| inner.strip_suffix('"'))
+                .or_else(|| {
+                    inner
+                        .strip_prefix('\'')
+                        .and_then(|inner| inner.strip_suffix('\''))
+                })?;
+
+            let mut chars = character.chars();
+            let character = chars.next()?.to_ascii_uppercase();
+            if chars.next().is_some() || !character.is_ascii_alphanumeric() {
+                return None;
+            }
+
+            return FromPrimitive::from_u32(character as u32);
+        }
+
+        (0..=u8::MAX)
+            .filter_map(VirtualKeyCode::from_u8)
+            .find(|vk| vk.to_gml() == value)
+    }
+}
+
+impl MouseButtonCode {
+    /// The GML constant for this mouse button, such as `mb_left`.
+    pub fn gml_constant(&self) -> &'static str {
+        match self {
+            MouseButtonCode::Left => "mb_left",
+            MouseButtonCode::Right => "mb_right",
+            MouseButtonCode::Middle => "mb_middle",
+        }
+    }
+
+    /// Parses a GML mouse button constant, such as `mb_left`.
+    pub fn from_gml_constant(value: &str) -> Option<Self> {
+        match value.trim() {
+            "mb_left" => Some(MouseButtonCode::Left),
+            "mb_right" => Some(MouseButtonCode::Right),
+            "mb_middle" => Some(MouseButtonCode::Middle),
+            _ => None,
+        }
+    }
+}
+
+/// A mouse button as GML functions such as `mouse_check_button` take it.
+/// Unlike [`MouseButtonCode`], which only names the buttons which mouse
+/// events can be for, this can also be `mb_any` or `mb_none`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum GmlMouseButton {
+    /// `mb_any`, which matches any button.
+    Any,
+    /// `mb_none`, which matches no button being held.
+    None,
+    /// A single button, such as `mb_left`.
+    Button(MouseButtonCode),
+}
+
+impl GmlMouseButton {
+    /// The GML constant for this mouse button, such as `mb_any`.
+    pub fn gml_constant(&self) -> &'static str {
+        match self {
+            GmlMouseButton::Any => "mb_any",
+            GmlMouseButton::None => "mb_none",
+            GmlMouseButton::Button(code) => code.gml_constant(),
+        }
+    }
+
+    /// Parses a GML mouse button constant, such as `mb_any` or `mb_left`.
+    pub fn from_gml_constant(value: &str) -> Option<Self> {
+        match value.trim() {
+            "mb_any" => Some(GmlMouseButton::Any),
+            "mb_none" => Some(GmlMouseButton::None),
+            value => MouseButtonCode::from_gml_constant(value).map(GmlMouseButton::Button),
+        }
+    }
+}
+
+impl From<MouseButtonCode> for GmlMouseButton {
+    fn from(code: MouseButtonCode) -> Self {
+        GmlMouseButton::Button(code)
+    }
+}
+
+impl MouseEvent {
+    /// The GML event number constant for this mouse event, such as
+    /// `ev_left_press` or `ev_global_right_release`.
+    pub fn gml_constant(&self) -> &'static str {
+        let (button, local, local_name, global_name) = match self {
+            MouseEvent::Down(button) => (
+                button,
+                button.local,
+                ["ev_left_button", "ev_right_button", "ev_middle_button"],
+                [
+                    "ev_global_left_button",
+                    "ev_global_right_button",
+                    "ev_global_middle_button",
+                ],
+            ),
+            MouseEvent::Pressed(button) => (
+                button,
+                button.local,
+                ["ev_left_press", "ev_right_press", "ev_middle_press"],
+                [
+                    "ev_global_left_press",
+                    "ev_global_right_press",
+                    "ev_global_middle_press",
+                ],
+            ),
+            MouseEvent::Released(button) => (
+                button,
+                button.local,
+                ["ev_left_release", "ev_right_release", "ev_middle_release"],
+                [
+                    "ev_global_left_release",
+                    "ev_global_right_release",
+                    "ev_global_middle_release",
+                ],
+            ),
+            MouseEvent::NoInput => return "ev_no_button",
+            MouseEvent::MouseEnter => return "ev_mouse_enter",
+            MouseEvent::MouseExit => return "ev_mouse_leave",
+            MouseEvent::MouseWheelUp => return "ev_mouse_wheel_up",
+            MouseEvent::MouseWheelDown => return "ev_mouse_wheel_down",
+        };
+
+        let index = button.mb_code as usize;
+        if local {
+            local_name[index]
+        } else {
+            global_name[index]
+        }
+    }
+}
+
+impl GestureEvent {
+    /// The GML event number constant for this gesture, such as
+    /// `ev_gesture_tap` or `ev_global_gesture_tap`.
+    pub fn gml_constant(&self) -> String {
+        let name = self.gesture.gml_name();
+
+        if self.local {
+            format!("ev_gesture_{}", name)
+        } else {
+            format!("ev_global_gesture_{}", name)
+        }
+    }
+
+    /// Parses a GML gesture event number constant, such as `ev_gesture_tap`
+    /// or `ev_global_gesture_tap`. This is the reverse of
+    /// [`gml_constant`](Self::gml_constant).
+    pub fn from_gml_constant(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (name, local) = match value.strip_prefix("ev_global_gesture_") {
+            Some(name) => (name, false),
+            None => (value.strip_prefix("ev_gesture_")?, true),
+        };
+
+        let gesture = (0..=u8::MAX)
+            .filter_map(Gesture::from_u8)
+            .find(|gesture| gesture.gml_name() == name)?;
+
+        Some(GestureEvent { gesture, local })
+    }
+}
+
+impl Gesture {
+    /// The name of the gesture within its GML constants, such as
+    /// `double_tap`.
+    fn gml_name(&self) -> &'static str {
+        match self {
+            Gesture::Tap => "tap",
+            Gesture::DoubleTap => "double_tap",
+            Gesture::DragStart => "drag_start",
+            Gesture::Dragging => "dragging",
+            Gesture::DragEnd => "drag_end",
+            Gesture::Flick => "flick",
+            Gesture::PinchStart => "pinch_start",
+            Gesture::PinchIn => "pinch_in",
+            Gesture::PinchOut => "pinch_out",
+            Gesture::PinchEnd => "pinch_end",
+            Gesture::RotateStart => "rotate_start",
+            Gesture::Rotating => "rotating",
+            Gesture::RotateEnd => "rotate_end",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::MouseButton;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn event_constants() {
+        let cases = [
+            (EventType::Step(Stage::Begin), "ev_step, ev_step_begin"),
+            (EventType::Alarm(3), "ev_alarm, 3"),
+            (
+                EventType::KeyDown(VirtualKeyCode::Left),
+                "ev_keyboard, vk_left",
+            ),
+            (
+                EventType::KeyPress(VirtualKeyCode::A),
+                "ev_keypress, ord(\"A\")",
+            ),
+            (
+                EventType::Mouse(MouseEvent::Released(MouseButton {
+                    mb_code: MouseButtonCode::Right,
+                    local: false,
+                })),
+                "ev_mouse, ev_global_right_release",
+            ),
+            (
+                EventType::Gesture(GestureEvent {
+                    gesture: Gesture::DoubleTap,
+                    local: true,
+                }),
+                "ev_gesture, ev_gesture_double_tap",
+            ),
+            (
+                EventType::Other(OtherEvent::UserEvent(4)),
+                "ev_other, ev_user4",
+            ),
+            (
+                EventType::Draw(DrawEvent::DrawGui(Stage::End)),
+                "ev_draw, ev_gui_end",
+            ),
+            (EventType::Async(AsyncEvent::Http), "ev_other, ev_async_web"),
+        ];
+
+        for (event, expected) in cases {
+            let gml = event.to_gml().unwrap();
+            assert_eq!(gml.to_string(), expected);
+            assert_eq!(
+                EventType::from_gml(gml.event_type, &gml.event_number).unwrap(),
+                event
+            );
+        }
+
+        assert_eq!(
+            EventType::from_gml("ev_other", "4").unwrap(),
+            EventType::Other(OtherEvent::RoomStart)
+        );
+        assert!(EventType::Collision.to_gml().is_none());
+        assert!(EventType::from_gml("ev_nothing", "0").is_err());
+        assert!(EventType::from_gml("ev_step", "ev_draw_begin").is_err());
+
+        let collision = EventKey::collision("obj_wall");
+        let gml = collision.to_gml().unwrap();
+        assert_eq!(gml.to_string(), "ev_collision, obj_wall");
+        assert_eq!(
+            EventKey::from_gml(gml.event_type, &gml.event_number).unwrap(),
+            collision
+        );
+    }
+
+    #[test]
+    fn key_symmetry() {
+        for code in 0..=u8::MAX {
+            if let Some(vk) = VirtualKeyCode::from_u8(code) {
+                assert_eq!(VirtualKeyCode::from_gml(&vk.to_gml()), Some(vk));
+            }
+        }
+
+        assert_eq!(
+            VirtualKeyCode::from_gml("ord('z')"),
+            Some(VirtualKeyCode::Z)
+        );
+        assert_eq!(
+            VirtualKeyCode::from_gml("ord(\"7\")"),
+            Some(VirtualKeyCode::Seven)
+        );
+        assert_eq!(VirtualKeyCode::from_gml("ord(\"AB\")"), None);
+        assert_eq!(VirtualKeyCode::from_gml("vk_nothing"), None);
+    }
+
+    #[test]
+    fn every_event_round_trips() {
+        for event_type in EventTypeNumber::ALL {
+            assert_eq!(
+                EventTypeNumber::from_gml_constant(event_type.gml_constant()),
+                Some(event_type)
+            );
+
+            for event_num in 0..=u8::MAX as usize {
+                let Ok(event) = EventType::try_from(EventIntermediary {
+                    event_type: event_type as usize,
+                    event_num,
+                }) else {
+                    continue;
+                };
+                let Some(gml) = event.to_gml() else {
+                    continue;
+                };
+
+                assert_eq!(
+                    EventType::from_gml(gml.event_type, &gml.event_number).unwrap(),
+                    event,
+                    "{}",
+                    gml
+                );
+            }
+        }
+
+        for code in [
+            MouseButtonCode::Left,
+            MouseButtonCode::Right,
+            MouseButtonCode::Middle,
+        ] {
+            assert_eq!(
+                MouseButtonCode::from_gml_constant(code.gml_constant()),
+                Some(code)
+            );
+            assert_eq!(
+                GmlMouseButton::from_gml_constant(code.gml_constant()),
+                Some(GmlMouseButton::Button(code))
+            );
+        }
+    }
+
+    #[test]
+    fn mouse_button_constants() {
+        for (button, constant) in [
+            (GmlMouseButton::Any, "mb_any"),
+            (GmlMouseButton::None, "mb_none"),
+            (GmlMouseButton::from(MouseButtonCode::Right), "mb_right"),
+        ] {
+            assert_eq!(button.gml_constant(), constant);
+            assert_eq!(GmlMouseButton::from_gml_constant(constant), Some(button));
+        }
+
+        assert_eq!(MouseButtonCode::from_gml_constant("mb_any"), None);
+        assert_eq!(GmlMouseButton::from_gml_constant("mb_side1"), None);
+    }
+
+    #[test]
+    fn gesture_constants() {
+        for gesture in (0..=u8::MAX).filter_map(Gesture::from_u8) {
+            for local in [true, false] {
+                let event = GestureEvent { gesture, local };
+                assert_eq!(
+                    GestureEvent::from_gml_constant(&event.gml_constant()),
+                    Some(event)
+                );
+            }
+        }
+
+        assert_eq!(
+            GestureEvent::from_gml_constant("ev_global_gesture_pinch_out"),
+            Some(GestureEvent {
+                gesture: Gesture::PinchOut,
+                local: false,
+            })
+        );
+        assert_eq!(GestureEvent::from_gml_constant("ev_gesture_wave"), None);
+        assert_eq!(GestureEvent::from_gml_constant("ev_tap"), None);
+    }
+}
@@ -0,0 +1,278 @@
+use super::Object;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+};
+
+/// An index of how a set of Objects inherit from each other, built from each
+/// Object's `parent_object_id`.
+///
+/// Objects are listed by name, and lists of names are sorted unless noted
+/// otherwise. Names which are given as a parent, but which are not in the
+/// set, still appear as parents, so their children can be found.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct ObjectHierarchy {
+    parents: BTreeMap<String, Option<String>>,
+    children: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl ObjectHierarchy {
+    /// Builds the hierarchy of the given Objects.
+    pub fn new<'a>(objects: impl IntoIterator<Item = &'a Object>) -> Self {
+        let mut output = Self::default();
+
+        for object in objects {
+            let name = object.common_data.name.clone();
+            let parent = object.parent_object_id.as_ref().map(|id| id.name.clone());
+
+            if let Some(parent) = &parent {
+                output
+                    .children
+                    .entry(parent.clone())
+                    .or_default()
+                    .insert(name.clone());
+            }
+            output.parents.insert(name, parent);
+        }
+
+        output
+    }
+
+    /// Returns `true` if an Object with the given name is in the hierarchy.
+    pub fn contains(&self, name: &str) -> bool {
+        self.parents.contains_key(name)
+    }
+
+    /// Returns the name of the Object's parent, if it has one.
+    pub fn parent(&self, name: &str) -> Option<&str> {
+        self.parents.get(name)?.as_deref()
+    }
+
+    /// Returns the Objects which inherit directly from `name`.
+    pub fn children(&self, name: &str) -> Vec<&str> {
+        self.children
+            .get(name)
+            .map(|children| children.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns every Object which inherits from `name`, directly or through
+    /// other Objects. Each child is followed by its own descendants before
+    /// the next child, so the output reads like an indented tree. If `name`
+    /// is part of a cycle, it is not listed as its own descendant.
+    pub fn descendants(&self, name: &str) -> Vec<&str> {
+        fn walk<'a>(
+            hierarchy: &'a ObjectHierarchy,
+            name: &str,
+            output: &mut Vec<&'a str>,
+            seen: &mut BTreeSet<&'a str>,
+        ) {
+            for child in hierarchy.children(name) {
+                if seen.insert(child) {
+                    output.push(child);
+                    walk(hierarchy, child, output, seen);
+                }
+            }
+        }
+
+        let mut output = vec![];
+        walk(self, name, &mut output, &mut BTreeSet::new());
+        output.retain(|descendant| *descendant != name);
+
+        output
+    }
+
+    /// Returns the parents of `name`, nearest first. The list stops early at
+    /// a parent which is not in the hierarchy, which is still included, or
+    /// before an Object would repeat because of a cycle.
+    pub fn ancestors(&self, name: &str) -> Vec<&str> {
+        let mut output: Vec<&str> = vec![];
+        let mut current = name;
+
+        while let Some(parent) = self.parent(current) {
+            if parent == name || output.contains(&parent) {
+                break;
+            }
+            output.push(parent);
+            current = parent;
+        }
+
+        output
+    }
+
+    /// Returns the Objects which have no parent.
+    pub fn roots(&self) -> Vec<&str> {
+        self.parents
+            .iter()
+            .filter(|(_, parent)| parent.is_none())
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Returns each Object whose parent is not in the hierarchy, paired with
+    /// the name of that parent.
+    pub fn missing_parents(&self) -> Vec<(&str, &str)> {
+        self.parents
+            .iter()
+            .filter_map(|(name, parent)| {
+                let parent = parent.as_deref()?;
+                (!self.contains(parent)).then_some((name.as_str(), parent))
+            })
+            .collect()
+    }
+
+    /// Returns each group of Objects which inherit from themselves. Each
+    /// cycle lists its Objects from child to parent, starting with the
+    /// Object whose name sorts first. Objects which inherit from a cycle,
+    /// without being part of it, are not listed.
+    pub fn cycles(&self) -> Vec<Vec<&str>> {
+        let mut output = vec![];
+        let mut done: BTreeSet<&str> = BTreeSet::new();
+
+        for start in self.parents.keys() {
+            let mut path: Vec<&str> = vec![];
+            let mut current = Some(start.as_str());
+
+            while let Some(name) = current {
+                if done.contains(name) {
+                    break;
+                }
+                if let Some(index) = path.iter().position(|seen| *seen == name) {
+                    let mut cycle = path[index..].to_vec();
+                    let first = (0..cycle.len()).min_by_key(|i| cycle[*i]).unwrap();
+                    cycle.rotate_left(first);
+                    output.push(cycle);
+                    break;
+                }
+
+                path.push(name);
+                current = self.parent(name);
+            }
+
+            done.extend(path);
+        }
+
+        output.sort();
+        output
+    }
+
+    /// Writes the hierarchy as a Graphviz `dot` graph, with an arrow from
+    /// each Object to its parent and parents drawn above their children.
+    /// Parents which are not in the hierarchy are drawn dashed.
+    pub fn to_dot(&self) -> String {
+        let mut output = String::from("digraph objects {\n    rankdir=BT;\n");
+
+        for name in self.parents.keys() {
+            writeln!(output, "    {};", dot_id(name)).unwrap();
+        }
+        let missing: BTreeSet<_> = self
+            .missing_parents()
+            .into_iter()
+            .map(|(_, parent)| parent)
+            .collect();
+        for parent in missing {
+            writeln!(output, "    {} [style=dashed];", dot_id(parent)).unwrap();
+        }
+        for (name, parent) in self.parents.iter() {
+            if let Some(parent) = parent {
+                writeln!(output, "    {} -> {};", dot_id(name), dot_id(parent)).unwrap();
+            }
+        }
+
+        output.push_str("}\n");
+        output
+    }
+}
+
+/// Quotes a name for use as a `dot` id.
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FilesystemPath;
+    use pretty_assertions::assert_eq;
+
+    fn object(name: &str, parent: Option<&str>) -> Object {
+        Object {
+            common_data: crate::CommonData::new(name.to_string()),
+            parent_object_id: parent.map(|parent| FilesystemPath::new("objects", parent)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn hierarchy() {
+        let objects = vec![
+            object("par_building", None),
+            object("par_modifiable_building", Some("par_building")),
+            object("obj_house", Some("par_modifiable_building")),
+            object("obj_shop", Some("par_modifiable_building")),
+            object("obj_tower", Some("par_building")),
+            object("obj_player", None),
+            object("obj_ghost", Some("par_missing")),
+        ];
+        let hierarchy = ObjectHierarchy::new(&objects);
+
+        assert_eq!(
+            hierarchy.children("par_modifiable_building"),
+            vec!["obj_house", "obj_shop"]
+        );
+        assert_eq!(
+            hierarchy.descendants("par_building"),
+            vec![
+                "obj_tower",
+                "par_modifiable_building",
+                "obj_house",
+                "obj_shop"
+            ]
+        );
+        assert_eq!(
+            hierarchy.ancestors("obj_house"),
+            vec!["par_modifiable_building", "par_building"]
+        );
+        assert_eq!(hierarchy.ancestors("obj_ghost"), vec!["par_missing"]);
+        assert_eq!(hierarchy.roots(), vec!["obj_player", "par_building"]);
+        assert_eq!(
+            hierarchy.missing_parents(),
+            vec![("obj_ghost", "par_missing")]
+        );
+        assert_eq!(hierarchy.children("par_missing"), vec!["obj_ghost"]);
+        assert!(hierarchy.cycles().is_empty());
+
+        let dot = hierarchy.to_dot();
+        assert!(dot.starts_with("digraph objects {\n"));
+        assert!(dot.contains("    \"obj_house\" -> \"par_modifiable_building\";\n"));
+        assert!(dot.contains("    \"par_missing\" [style=dashed];\n"));
+        assert!(!dot.contains("\"obj_player\" ->"));
+    }
+
+    #[test]
+    fn cycles() {
+        let objects = vec![
+            object("obj_c", Some("obj_a")),
+            object("obj_b", Some("obj_c")),
+            object("obj_a", Some("obj_b")),
+            object("obj_child", Some("obj_b")),
+            object("obj_self", Some("obj_self")),
+        ];
+        let hierarchy = ObjectHierarchy::new(&objects);
+
+        assert_eq!(
+            hierarchy.cycles(),
+            vec![vec!["obj_a", "obj_b", "obj_c"], vec!["obj_self"]]
+        );
+        assert_eq!(
+            hierarchy.ancestors("obj_child"),
+            vec!["obj_b", "obj_c", "obj_a"]
+        );
+        assert_eq!(hierarchy.ancestors("obj_a"), vec!["obj_b", "obj_c"]);
+        assert!(hierarchy.roots().is_empty());
+        assert_eq!(
+            hierarchy.descendants("obj_a"),
+            vec!["obj_c", "obj_b", "obj_child"]
+        );
+    }
+}
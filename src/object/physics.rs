@@ -0,0 +1,366 @@
+use super::{Object, PhysicsShape, PhysicsVec2};
+use crate::Sprite;
+
+/// The geometry described by an Object's `physics_shape_points`, interpreted
+/// according to its `physics_shape`. Coordinates are in pixels, relative to
+/// the top left of the Object's Sprite.
+#[derive(Debug, PartialEq, Clone)]
+pub enum PhysicsGeometry {
+    /// A circle, stored as its centre followed by a point on its edge.
+    Circle { centre: PhysicsVec2, radius: f32 },
+    /// A convex polygon, wound clockwise on screen. Boxes are stored as
+    /// their four corners.
+    Polygon(Vec<PhysicsVec2>),
+}
+
+/// Errors in an Object's physics shape which stop it from working in game.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, thiserror::Error)]
+pub enum PhysicsShapeError {
+    #[error("a {shape:?} shape needs {expected} points, but has {found}")]
+    WrongPointCount {
+        shape: PhysicsShape,
+        expected: usize,
+        found: usize,
+    },
+    #[error("a convex shape needs at least 3 points, but has {0}")]
+    TooFewVertices(usize),
+    #[error("a convex shape can have at most {max} points, but has {0}", max = PhysicsGeometry::MAX_VERTICES)]
+    TooManyVertices(usize),
+    #[error("the shape has no area")]
+    Degenerate,
+    #[error("the shape is not convex")]
+    NotConvex,
+    #[error("the shape's points are wound anti-clockwise")]
+    AntiClockwise,
+}
+
+impl PhysicsGeometry {
+    /// The most vertices a polygon can have, which is Box2D's limit.
+    pub const MAX_VERTICES: usize = 8;
+
+    /// The area of the shape, in square pixels.
+    pub fn area(&self) -> f64 {
+        match self {
+            PhysicsGeometry::Circle { radius, .. } => {
+                std::f64::consts::PI * f64::from(*radius) * f64::from(*radius)
+            }
+            PhysicsGeometry::Polygon(points) => signed_area(points).abs(),
+        }
+    }
+
+    /// The centre of mass of the shape, in pixels.
+    pub fn centroid(&self) -> PhysicsVec2 {
+        match self {
+            PhysicsGeometry::Circle { centre, .. } => *centre,
+            PhysicsGeometry::Polygon(points) => {
+                let area = signed_area(points);
+                if area == 0.0 {
+                    return PhysicsVec2::default();
+                }
+
+                let (mut x, mut y) = (0.0, 0.0);
+                for (a, b) in edges(points) {
+                    let cross = cross(a, b);
+                    x += (f64::from(a.x) + f64::from(b.x)) * cross;
+                    y += (f64::from(a.y) + f64::from(b.y)) * cross;
+                }
+
+                PhysicsVec2 {
+                    x: (x / (6.0 * area)) as f32,
+                    y: (y / (6.0 * area)) as f32,
+                }
+            }
+        }
+    }
+}
+
+impl Object {
+    /// Interprets `physics_shape_points` according to `physics_shape`, and
+    /// checks that the runtime can use the shape: circles need their centre
+    /// and a point on their edge, boxes their four corners, and convex shapes
+    /// between 3 and [`PhysicsGeometry::MAX_VERTICES`] points. Boxes and
+    /// convex shapes must be convex, and wound clockwise on screen.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the shape would break in game.
+    pub fn physics_geometry(&self) -> Result<PhysicsGeometry, PhysicsShapeError> {
+        let points = &self.physics_shape_points;
+
+        let expected = match self.physics_shape {
+            PhysicsShape::Circle => Some(2),
+            PhysicsShape::Box => Some(4),
+            PhysicsShape::ConvexShape => None,
+        };
+        if let Some(expected) = expected
+            && points.len() != expected
+        {
+            return Err(PhysicsShapeError::WrongPointCount {
+                shape: self.physics_shape,
+                expected,
+                found: points.len(),
+            });
+        }
+
+        if self.physics_shape == PhysicsShape::Circle {
+            let (centre, edge) = (points[0], points[1]);
+            let radius = (edge.x - centre.x).hypot(edge.y - centre.y);
+            if radius <= 0.0 {
+                return Err(PhysicsShapeError::Degenerate);
+            }
+
+            return Ok(PhysicsGeometry::Circle { centre, radius });
+        }
+
+        if points.len() < 3 {
+            return Err(PhysicsShapeError::TooFewVertices(points.len()));
+        }
+        if points.len() > PhysicsGeometry::MAX_VERTICES {
+            return Err(PhysicsShapeError::TooManyVertices(points.len()));
+        }
+
+        let area = signed_area(points);
+        if area == 0.0 {
+            return Err(PhysicsShapeError::Degenerate);
+        }
+        if !is_convex(points) {
+            return Err(PhysicsShapeError::NotConvex);
+        }
+        if area < 0.0 {
+            return Err(PhysicsShapeError::AntiClockwise);
+        }
+
+        Ok(PhysicsGeometry::Polygon(points.clone()))
+    }
+
+    /// The mass of the Object's physics shape: its `physics_density` times
+    /// its area in square metres. `pixels_to_metres` is the room's
+    /// `PhysicsWorldPixToMetres`, which is `0.1` by default.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the shape would break in game; see
+    /// [`physics_geometry`](Self::physics_geometry).
+    pub fn physics_mass(&self, pixels_to_metres: f64) -> Result<f64, PhysicsShapeError> {
+        let area = self.physics_geometry()?.area();
+
+        Ok(self.physics_density * area * pixels_to_metres * pixels_to_metres)
+    }
+
+    /// Replaces the Object's physics shape with a default shape of the given
+    /// kind, fitted to the bounding box of `sprite`, as the IDE does when a
+    /// shape is first made. Circles fill the smaller side of the box, while
+    /// boxes and convex shapes are the box itself.
+    pub fn set_default_physics_shape(&mut self, shape: PhysicsShape, sprite: &Sprite) {
+        let left = sprite.bbox_left as f32;
+        let top = sprite.bbox_top as f32;
+        let right = (sprite.bbox_right + 1) as f32;
+        let bottom = (sprite.bbox_bottom + 1) as f32;
+
+        self.physics_shape = shape;
+        self.physics_shape_points = match shape {
+            PhysicsShape::Circle => {
+                let centre = PhysicsVec2 {
+                    x: (left + right) / 2.0,
+                    y: (top + bottom) / 2.0,
+                };
+                let radius = (right - left).min(bottom - top) / 2.0;
+
+                vec![
+                    centre,
+                    PhysicsVec2 {
+                        x: centre.x + radius,
+                        y: centre.y,
+                    },
+                ]
+            }
+            PhysicsShape::Box | PhysicsShape::ConvexShape => vec![
+                PhysicsVec2 { x: left, y: top },
+                PhysicsVec2 { x: right, y: top },
+                PhysicsVec2 {
+                    x: right,
+                    y: bottom,
+                },
+                PhysicsVec2 { x: left, y: bottom },
+            ],
+        };
+    }
+}
+
+/// Each edge of a polygon, as a pair of its start and end points.
+fn edges(points: &[PhysicsVec2]) -> impl Iterator<Item = (PhysicsVec2, PhysicsVec2)> + '_ {
+    points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+}
+
+fn cross(a: PhysicsVec2, b: PhysicsVec2) -> f64 {
+    f64::from(a.x) * f64::from(b.y) - f64::from(b.x) * f64::from(a.y)
+}
+
+/// The area of a polygon, which is positive if the polygon is wound
+/// clockwise on screen, where `y` points down.
+fn signed_area(points: &[PhysicsVec2]) -> f64 {
+    edges(points).map(|(a, b)| cross(a, b)).sum::<f64>() / 2.0
+}
+
+/// Whether a polygon is convex: every corner turns the same way, and the
+/// polygon only goes around once.
+fn is_convex(points: &[PhysicsVec2]) -> bool {
+    let mut sign = 0.0;
+    let mut turning = 0.0;
+
+    for i in 0..points.len() {
+        let a = points[i];
+        let b = points[(i + 1) % points.len()];
+        let c = points[(i + 2) % points.len()];
+
+        let ab = (f64::from(b.x - a.x), f64::from(b.y - a.y));
+        let bc = (f64::from(c.x - b.x), f64::from(c.y - b.y));
+        let turn = ab.0 * bc.1 - ab.1 * bc.0;
+        if turn != 0.0 {
+            if sign * turn < 0.0 {
+                return false;
+            }
+            sign = turn;
+        }
+
+        turning += turn.atan2(ab.0 * bc.0 + ab.1 * bc.1);
+    }
+
+    turning.abs() < std::f64::consts::TAU + 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::num::NonZeroUsize;
+
+    fn object(shape: PhysicsShape, points: &[(f32, f32)]) -> Object {
+        Object {
+            physics_shape: shape,
+            physics_shape_points: points.iter().map(|&(x, y)| PhysicsVec2 { x, y }).collect(),
+            physics_density: 0.5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn geometry() {
+        let square = object(
+            PhysicsShape::Box,
+            &[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)],
+        );
+        let geometry = square.physics_geometry().unwrap();
+        assert_eq!(geometry.area(), 100.0);
+        assert_eq!(geometry.centroid(), PhysicsVec2 { x: 5.0, y: 5.0 });
+        assert_eq!(square.physics_mass(0.1).unwrap(), 0.5);
+
+        let circle = object(PhysicsShape::Circle, &[(5.0, 5.0), (5.0, 8.0)]);
+        assert_eq!(
+            circle.physics_geometry().unwrap(),
+            PhysicsGeometry::Circle {
+                centre: PhysicsVec2 { x: 5.0, y: 5.0 },
+                radius: 3.0
+            }
+        );
+
+        let triangle = object(
+            PhysicsShape::ConvexShape,
+            &[(0.0, 0.0), (6.0, 0.0), (0.0, 6.0)],
+        );
+        let geometry = triangle.physics_geometry().unwrap();
+        assert_eq!(geometry.area(), 18.0);
+        assert_eq!(geometry.centroid(), PhysicsVec2 { x: 2.0, y: 2.0 });
+    }
+
+    #[test]
+    fn errors() {
+        let error = |shape, points: &[(f32, f32)]| object(shape, points).physics_geometry();
+
+        assert_eq!(
+            error(PhysicsShape::Box, &[(0.0, 0.0)]),
+            Err(PhysicsShapeError::WrongPointCount {
+                shape: PhysicsShape::Box,
+                expected: 4,
+                found: 1
+            })
+        );
+        assert_eq!(
+            error(
+                PhysicsShape::ConvexShape,
+                &[
+                    (0.0, 0.0),
+                    (10.0, 0.0),
+                    (5.0, 2.0),
+                    (10.0, 10.0),
+                    (0.0, 10.0)
+                ]
+            ),
+            Err(PhysicsShapeError::NotConvex)
+        );
+        assert_eq!(
+            error(
+                PhysicsShape::ConvexShape,
+                &[(0.0, 0.0), (0.0, 6.0), (6.0, 0.0)]
+            ),
+            Err(PhysicsShapeError::AntiClockwise)
+        );
+        assert_eq!(
+            error(
+                PhysicsShape::ConvexShape,
+                &[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]
+            ),
+            Err(PhysicsShapeError::Degenerate)
+        );
+
+        let star: Vec<_> = (0..5)
+            .map(|i| {
+                let angle = (i * 2) as f32 * std::f32::consts::TAU / 5.0;
+                (angle.cos() * 10.0, angle.sin() * 10.0)
+            })
+            .collect();
+        assert_eq!(
+            error(PhysicsShape::ConvexShape, &star),
+            Err(PhysicsShapeError::NotConvex)
+        );
+
+        let nonagon: Vec<_> = (0..9)
+            .map(|i| {
+                let angle = i as f32 * std::f32::consts::TAU / 9.0;
+                (angle.cos() * 10.0, angle.sin() * 10.0)
+            })
+            .collect();
+        assert_eq!(
+            error(PhysicsShape::ConvexShape, &nonagon),
+            Err(PhysicsShapeError::TooManyVertices(9))
+        );
+    }
+
+    #[test]
+    fn default_shape() {
+        let sprite = Sprite::new(
+            "spr_test",
+            NonZeroUsize::new(32).unwrap(),
+            NonZeroUsize::new(16).unwrap(),
+        );
+        let mut object = Object::default();
+
+        object.set_default_physics_shape(PhysicsShape::Box, &sprite);
+        assert_eq!(
+            object.physics_geometry().unwrap().centroid(),
+            PhysicsVec2 { x: 16.0, y: 8.0 }
+        );
+
+        object.set_default_physics_shape(PhysicsShape::Circle, &sprite);
+        assert_eq!(
+            object.physics_geometry().unwrap(),
+            PhysicsGeometry::Circle {
+                centre: PhysicsVec2 { x: 16.0, y: 8.0 },
+                radius: 8.0
+            }
+        );
+    }
+}
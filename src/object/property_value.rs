@@ -0,0 +1,430 @@
+use super::{ObjectOverrideProperty, ObjectProperty, ObjectPropertyTypes};
+use crate::GmEncodedColor;
+use std::fmt;
+
+/// The value of an [`ObjectProperty`], parsed according to its `var_type`.
+///
+/// The `Display` implementation writes the value exactly as the Gms2 IDE
+/// serializes it into `ObjectProperty::value`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum PropertyValue {
+    /// A GML real, such as `0.5`.
+    Real(f64),
+    /// A whole number.
+    Integer(i64),
+    /// A string, which is written with quotes around it.
+    String(String),
+    /// A boolean, which is written as `True` or `False`.
+    Boolean(bool),
+    /// A GML expression, which is written as is.
+    Expression(String),
+    /// The name of an asset, such as `spr_player`.
+    Asset(String),
+    /// The selected items of a list. A list which is not `multiselect` has
+    /// exactly one item selected, while a `multiselect` list may have none,
+    /// which is written as an empty string.
+    List(Vec<String>),
+    /// A colour, which is written as `$AABBGGRR`.
+    Colour(GmEncodedColor),
+}
+
+/// Errors which can occur when parsing or setting the value of a property.
+#[derive(Debug, PartialEq, Clone, thiserror::Error)]
+pub enum PropertyValueError {
+    #[error("`{value}` is not a valid {var_type:?} value")]
+    Invalid {
+        var_type: ObjectPropertyTypes,
+        value: String,
+    },
+    #[error("expected a {expected:?} value, found a {found:?} value")]
+    WrongType {
+        expected: ObjectPropertyTypes,
+        found: ObjectPropertyTypes,
+    },
+    #[error("{value} is outside of the range {min} to {max}")]
+    OutOfRange { value: f64, min: f64, max: f64 },
+    #[error("`{0}` is not one of the list's items")]
+    NotInList(String),
+    #[error("list allows exactly one item to be selected, but {0} were selected")]
+    NotMultiselect(usize),
+}
+
+impl PropertyValue {
+    /// Parses a serialized property value of the given type.
+    ///
+    /// Strings may be given with or without their quotes. Colours may leave
+    /// out their alpha, as in `$BBGGRR`, in which case they are opaque.
+    ///
+    /// # Errors
+    ///
+    /// Errors if `value` is not a valid value of `var_type`.
+    pub fn parse(var_type: ObjectPropertyTypes, value: &str) -> Result<Self, PropertyValueError> {
+        let invalid = || PropertyValueError::Invalid {
+            var_type,
+            value: value.to_string(),
+        };
+        let trimmed = value.trim();
+
+        let output = match var_type {
+            ObjectPropertyTypes::Real => Self::Real(trimmed.parse().map_err(|_| invalid())?),
+            ObjectPropertyTypes::Integer => Self::Integer(trimmed.parse().map_err(|_| invalid())?),
+            ObjectPropertyTypes::String => Self::String(unquote(value)),
+            ObjectPropertyTypes::Boolean => match trimmed {
+                "True" | "true" => Self::Boolean(true),
+                "False" | "false" => Self::Boolean(false),
+                _ => return Err(invalid()),
+            },
+            ObjectPropertyTypes::Expression => Self::Expression(value.to_string()),
+            ObjectPropertyTypes::Asset => Self::Asset(trimmed.to_string()),
+            ObjectPropertyTypes::List => {
+                let items = match trimmed.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                    _ if trimmed.is_empty() => vec![],
+                    Some(items) => items
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|item| !item.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                    None => vec![trimmed.to_string()],
+                };
+
+                Self::List(items)
+            }
+            ObjectPropertyTypes::Colour => {
+                let hex = trimmed.strip_prefix('$').ok_or_else(invalid)?;
+                let colour = u32::from_str_radix(hex, 16).map_err(|_| invalid())?;
+                let colour = match hex.len() {
+                    6 => colour | 0xFF00_0000,
+                    8 => colour,
+                    _ => return Err(invalid()),
+                };
+
+                Self::Colour(GmEncodedColor::new(colour.into()))
+            }
+        };
+
+        Ok(output)
+    }
+
+    /// The type of property which can hold this value.
+    pub fn var_type(&self) -> ObjectPropertyTypes {
+        match self {
+            Self::Real(_) => ObjectPropertyTypes::Real,
+            Self::Integer(_) => ObjectPropertyTypes::Integer,
+            Self::String(_) => ObjectPropertyTypes::String,
+            Self::Boolean(_) => ObjectPropertyTypes::Boolean,
+            Self::Expression(_) => ObjectPropertyTypes::Expression,
+            Self::Asset(_) => ObjectPropertyTypes::Asset,
+            Self::List(_) => ObjectPropertyTypes::List,
+            Self::Colour(_) => ObjectPropertyTypes::Colour,
+        }
+    }
+}
+
+impl fmt::Display for PropertyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Real(value) => write!(f, "{}", value),
+            Self::Integer(value) => write!(f, "{}", value),
+            Self::String(value) => {
+                write!(
+                    f,
+                    "\"{}\"",
+                    value.replace('\\', "\\\\").replace('"', "\\\"")
+                )
+            }
+            Self::Boolean(true) => write!(f, "True"),
+            Self::Boolean(false) => write!(f, "False"),
+            Self::Expression(value) | Self::Asset(value) => write!(f, "{}", value),
+            Self::List(items) => match items.as_slice() {
+                [] => Ok(()),
+                [item] => write!(f, "{}", item),
+                items => write!(f, "[{}]", items.join(",")),
+            },
+            Self::Colour(colour) => write!(f, "${:08X}", colour.inner() as u32),
+        }
+    }
+}
+
+/// Removes the quotes from around a GML string, and unescapes any quotes or
+/// backslashes inside of it. Strings without quotes are returned as is.
+fn unquote(value: &str) -> String {
+    let Some(inner) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    else {
+        return value.to_string();
+    };
+
+    let mut output = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped @ ('"' | '\\')) => output.push(escaped),
+                Some(other) => {
+                    output.push('\\');
+                    output.push(other);
+                }
+                None => output.push('\\'),
+            },
+            c => output.push(c),
+        }
+    }
+
+    output
+}
+
+impl ObjectProperty {
+    /// Parses `value` according to `var_type`.
+    ///
+    /// # Errors
+    ///
+    /// Errors if `value` is not a valid value of `var_type`.
+    pub fn typed_value(&self) -> Result<PropertyValue, PropertyValueError> {
+        PropertyValue::parse(self.var_type, &self.value)
+    }
+
+    /// Checks that `value` can be given to this property: that it has the
+    /// right type, that numbers are within the range if `range_enabled` is
+    /// set, and that lists only select their own items, and only select more
+    /// than one item if they are `multiselect`.
+    ///
+    /// # Errors
+    ///
+    /// Errors if `value` cannot be given to this property.
+    pub fn validate_value(&self, value: &PropertyValue) -> Result<(), PropertyValueError> {
+        if value.var_type() != self.var_type {
+            return Err(PropertyValueError::WrongType {
+                expected: self.var_type,
+                found: value.var_type(),
+            });
+        }
+
+        let number = match value {
+            PropertyValue::Real(number) => Some(*number),
+            PropertyValue::Integer(number) => Some(*number as f64),
+            _ => None,
+        };
+        if let Some(number) = number.filter(|_| self.range_enabled)
+            && !(self.range_min..=self.range_max).contains(&number)
+        {
+            return Err(PropertyValueError::OutOfRange {
+                value: number,
+                min: self.range_min,
+                max: self.range_max,
+            });
+        }
+
+        if let PropertyValue::List(items) = value {
+            if !self.multiselect && items.len() != 1 {
+                return Err(PropertyValueError::NotMultiselect(items.len()));
+            }
+            if let Some(item) = items.iter().find(|item| !self.list_items.contains(item)) {
+                return Err(PropertyValueError::NotInList(item.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates `value` with [`validate_value`](Self::validate_value), and
+    /// then sets it as the property's default value, formatted as the IDE
+    /// does.
+    ///
+    /// # Errors
+    ///
+    /// Errors if `value` cannot be given to this property.
+    pub fn set_value(&mut self, value: &PropertyValue) -> Result<(), PropertyValueError> {
+        self.validate_value(value)?;
+        self.value = value.to_string();
+
+        Ok(())
+    }
+}
+
+impl ObjectOverrideProperty {
+    /// Parses `value` according to the `var_type` of `definition`, which
+    /// should be the property this overrides.
+    ///
+    /// # Errors
+    ///
+    /// Errors if `value` is not a valid value of the property's type.
+    pub fn typed_value(
+        &self,
+        definition: &ObjectProperty,
+    ) -> Result<PropertyValue, PropertyValueError> {
+        PropertyValue::parse(definition.var_type, &self.value)
+    }
+
+    /// Validates `value` against `definition`, which should be the property
+    /// this overrides, and then sets it as the overriding value. See
+    /// [`ObjectProperty::set_value`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if `value` cannot be given to the property.
+    pub fn set_value(
+        &mut self,
+        definition: &ObjectProperty,
+        value: &PropertyValue,
+    ) -> Result<(), PropertyValueError> {
+        definition.validate_value(value)?;
+        self.value = value.to_string();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn property(var_type: ObjectPropertyTypes, value: &str) -> ObjectProperty {
+        ObjectProperty {
+            var_type,
+            value: value.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn symmetry() {
+        let values = [
+            (ObjectPropertyTypes::Real, "0.5", PropertyValue::Real(0.5)),
+            (ObjectPropertyTypes::Real, "-3", PropertyValue::Real(-3.0)),
+            (
+                ObjectPropertyTypes::Integer,
+                "42",
+                PropertyValue::Integer(42),
+            ),
+            (
+                ObjectPropertyTypes::String,
+                r#""say \"hi\"""#,
+                PropertyValue::String(r#"say "hi""#.to_string()),
+            ),
+            (
+                ObjectPropertyTypes::Boolean,
+                "True",
+                PropertyValue::Boolean(true),
+            ),
+            (
+                ObjectPropertyTypes::Expression,
+                "room_width / 2",
+                PropertyValue::Expression("room_width / 2".to_string()),
+            ),
+            (
+                ObjectPropertyTypes::Asset,
+                "spr_player",
+                PropertyValue::Asset("spr_player".to_string()),
+            ),
+            (
+                ObjectPropertyTypes::List,
+                "Building.Bridge",
+                PropertyValue::List(vec!["Building.Bridge".to_string()]),
+            ),
+            (ObjectPropertyTypes::List, "", PropertyValue::List(vec![])),
+            (
+                ObjectPropertyTypes::List,
+                "[Red,Blue]",
+                PropertyValue::List(vec!["Red".to_string(), "Blue".to_string()]),
+            ),
+            (
+                ObjectPropertyTypes::Colour,
+                "$FF0000FF",
+                PropertyValue::Colour(GmEncodedColor::from_rgb(255, 0, 0)),
+            ),
+        ];
+
+        for (var_type, value, expected) in values {
+            let property = property(var_type, value);
+            let typed = property.typed_value().unwrap();
+            assert_eq!(typed, expected);
+            assert_eq!(typed.to_string(), value);
+        }
+
+        assert_eq!(
+            PropertyValue::parse(ObjectPropertyTypes::String, "plain").unwrap(),
+            PropertyValue::String("plain".to_string())
+        );
+        assert_eq!(
+            PropertyValue::parse(ObjectPropertyTypes::Colour, "$00FF00").unwrap(),
+            PropertyValue::Colour(GmEncodedColor::from_rgb(0, 255, 0))
+        );
+        assert_eq!(
+            PropertyValue::parse(ObjectPropertyTypes::List, "  ").unwrap(),
+            PropertyValue::List(vec![])
+        );
+        assert!(PropertyValue::parse(ObjectPropertyTypes::Boolean, "yes").is_err());
+        assert!(PropertyValue::parse(ObjectPropertyTypes::Integer, "1.5").is_err());
+    }
+
+    #[test]
+    fn set_value() {
+        let mut real = property(ObjectPropertyTypes::Real, "0");
+        real.range_enabled = true;
+        real.range_min = 0.0;
+        real.range_max = 10.0;
+
+        real.set_value(&PropertyValue::Real(2.5)).unwrap();
+        assert_eq!(real.value, "2.5");
+        assert_eq!(
+            real.set_value(&PropertyValue::Real(11.0)),
+            Err(PropertyValueError::OutOfRange {
+                value: 11.0,
+                min: 0.0,
+                max: 10.0
+            })
+        );
+        assert!(matches!(
+            real.set_value(&PropertyValue::Integer(1)),
+            Err(PropertyValueError::WrongType { .. })
+        ));
+
+        let mut list = property(ObjectPropertyTypes::List, "Red");
+        list.list_items = vec!["Red".to_string(), "Blue".to_string()];
+        let both = PropertyValue::List(vec!["Red".to_string(), "Blue".to_string()]);
+        assert_eq!(
+            list.set_value(&both),
+            Err(PropertyValueError::NotMultiselect(2))
+        );
+        assert_eq!(
+            list.set_value(&PropertyValue::List(vec!["Green".to_string()])),
+            Err(PropertyValueError::NotInList("Green".to_string()))
+        );
+
+        list.multiselect = true;
+        list.set_value(&both).unwrap();
+        assert_eq!(list.value, "[Red,Blue]");
+
+        let mut override_property = ObjectOverrideProperty::default();
+        override_property
+            .set_value(&list, &PropertyValue::List(vec!["Blue".to_string()]))
+            .unwrap();
+        assert_eq!(override_property.value, "Blue");
+        assert_eq!(
+            override_property.typed_value(&list).unwrap(),
+            PropertyValue::List(vec!["Blue".to_string()])
+        );
+    }
+
+    #[test]
+    fn empty_multiselect() {
+        let text = include_str!("../../data/objects/obj_map_node.yy");
+        let text = crate::utils::TrailingCommaUtility::new().clear_trailing_comma(text);
+        let object: crate::Object = serde_json::from_str(&text).unwrap();
+
+        let mut locations = object
+            .properties
+            .into_iter()
+            .find(|property| property.common_data.name == "locations")
+            .unwrap();
+        assert!(locations.multiselect);
+
+        let value = locations.typed_value().unwrap();
+        assert_eq!(value, PropertyValue::List(vec![]));
+        locations.set_value(&value).unwrap();
+        assert_eq!(locations.value, "");
+    }
+}
@@ -0,0 +1,432 @@
+use super::{EventKey, Object, ObjectEvent, ObjectProperty};
+use crate::FilesystemPath;
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::BuildHasher,
+};
+
+/// A collection of Objects which can be looked up by name, used to follow
+/// `parent_object_id`s.
+pub trait ObjectLookup {
+    /// Returns the Object with the given name, if there is one.
+    fn get_object(&self, name: &str) -> Option<&Object>;
+}
+
+impl<S: BuildHasher> ObjectLookup for HashMap<String, Object, S> {
+    fn get_object(&self, name: &str) -> Option<&Object> {
+        self.get(name)
+    }
+}
+
+impl ObjectLookup for BTreeMap<String, Object> {
+    fn get_object(&self, name: &str) -> Option<&Object> {
+        self.get(name)
+    }
+}
+
+impl ObjectLookup for [Object] {
+    fn get_object(&self, name: &str) -> Option<&Object> {
+        self.iter().find(|object| object.common_data.name == name)
+    }
+}
+
+/// Errors which can occur when following an Object's parents.
+#[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+pub enum ResolveError {
+    #[error("object `{0}` does not exist")]
+    MissingObject(String),
+    #[error("object `{object}` has parent `{parent}`, which does not exist")]
+    MissingParent { object: String, parent: String },
+    #[error("objects inherit from themselves: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+}
+
+/// An Object with everything it inherits from its parents filled in. See
+/// [`Object::resolve`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct ResolvedObject<'a> {
+    /// The Object followed by each of its parents, ending with an Object which
+    /// has no parent.
+    pub chain: Vec<&'a Object>,
+
+    /// Every property the Object has, whether defined by it or one of its
+    /// parents, with overrides applied. Properties are ordered from the
+    /// top-most parent down to the Object, as the IDE lists them.
+    pub properties: Vec<ResolvedProperty<'a>>,
+
+    /// Every event the Object runs. The Object's own events come first, in
+    /// the order of its `event_list`, followed by inherited events.
+    pub events: Vec<ResolvedEvent<'a>>,
+
+    /// The Sprite the Object uses. GameMaker does not inherit Sprites, so
+    /// this is always the Object's own `sprite_id`.
+    pub sprite_id: Option<&'a FilesystemPath>,
+
+    /// The Sprite used as the Object's collision mask. This is the Object's
+    /// own `sprite_mask_id`, or `sprite_id` if it does not set a mask, as
+    /// with "Same As Sprite" in the IDE. Masks are not inherited either.
+    pub sprite_mask_id: Option<&'a FilesystemPath>,
+
+    /// The Object itself, if it is a physics object, so that its `physics_*`
+    /// fields apply. Physics settings are not inherited, so this is `None`
+    /// if the Object does not use physics, even if a parent does.
+    pub physics: Option<&'a Object>,
+}
+
+/// A property of a [`ResolvedObject`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct ResolvedProperty<'a> {
+    /// The definition of the property, giving its type and default value.
+    pub definition: &'a ObjectProperty,
+
+    /// The Object which defines the property.
+    pub defined_by: &'a Object,
+
+    /// The value of the property, after overrides. This is the serialized
+    /// value, exactly as it appears in the `yy` files.
+    pub value: &'a str,
+
+    /// The Object whose override gives the property its value, or `None` if
+    /// it has its default value.
+    pub overridden_by: Option<&'a Object>,
+}
+
+/// An event of a [`ResolvedObject`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct ResolvedEvent<'a> {
+    /// The event as it appears in the `event_list` of `defined_by`.
+    pub event: &'a ObjectEvent,
+
+    /// The Object whose GML file for this event is run.
+    pub defined_by: &'a Object,
+
+    /// How the Object came to have this event.
+    pub source: EventSource<'a>,
+}
+
+/// Where a [`ResolvedEvent`] comes from.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum EventSource<'a> {
+    /// The event is only defined by the Object itself.
+    Own,
+    /// The Object defines the event, replacing the event of the given parent.
+    /// The parent's event only runs if the Object calls `event_inherited`.
+    Overrides(&'a Object),
+    /// The Object does not define the event, so it runs the event of the
+    /// given parent.
+    Inherited(&'a Object),
+}
+
+impl Object {
+    /// Returns the Object followed by each of its parents, ending with an
+    /// Object which has no parent.
+    ///
+    /// # Errors
+    ///
+    /// Errors if a parent cannot be found in `lookup`, or if an Object
+    /// inherits from itself.
+    pub fn inheritance_chain<'a, L: ObjectLookup + ?Sized>(
+        &'a self,
+        lookup: &'a L,
+    ) -> Result<Vec<&'a Object>, ResolveError> {
+        let mut chain = vec![self];
+        let mut current = self;
+
+        while let Some(parent) = &current.parent_object_id {
+            if let Some(index) = chain
+                .iter()
+                .position(|object| object.common_data.name == parent.name)
+            {
+                let mut cycle: Vec<String> = chain[index..]
+                    .iter()
+                    .map(|object| object.common_data.name.clone())
+                    .collect();
+                cycle.push(parent.name.clone());
+
+                return Err(ResolveError::Cycle(cycle));
+            }
+
+            current =
+                lookup
+                    .get_object(&parent.name)
+                    .ok_or_else(|| ResolveError::MissingParent {
+                        object: current.common_data.name.clone(),
+                        parent: parent.name.clone(),
+                    })?;
+            chain.push(current);
+        }
+
+        Ok(chain)
+    }
+
+    /// Resolves everything the Object inherits from its parents: its
+    /// properties with overrides applied and the events it runs. Its Sprites
+    /// and physics settings are included too, but GameMaker does not inherit
+    /// those, so they always come from the Object itself.
+    ///
+    /// # Errors
+    ///
+    /// Errors if a parent cannot be found in `lookup`, or if an Object
+    /// inherits from itself.
+    pub fn resolve<'a, L: ObjectLookup + ?Sized>(
+        &'a self,
+        lookup: &'a L,
+    ) -> Result<ResolvedObject<'a>, ResolveError> {
+        let chain = self.inheritance_chain(lookup)?;
+
+        let mut properties: Vec<ResolvedProperty<'a>> = vec![];
+        for object in chain.iter().rev() {
+            for definition in object.properties.iter() {
+                let property = ResolvedProperty {
+                    definition,
+                    defined_by: object,
+                    value: &definition.value,
+                    overridden_by: None,
+                };
+
+                match properties
+                    .iter_mut()
+                    .find(|p| p.definition.common_data.name == definition.common_data.name)
+                {
+                    Some(existing) => *existing = property,
+                    None => properties.push(property),
+                }
+            }
+
+            for override_property in object.overridden_properties.iter() {
+                let Some(name) = override_property.property_id.as_ref().map(|id| &id.name) else {
+                    continue;
+                };
+
+                if let Some(property) = properties
+                    .iter_mut()
+                    .find(|p| p.definition.common_data.name == *name)
+                {
+                    property.value = &override_property.value;
+                    property.overridden_by = Some(object);
+                }
+            }
+        }
+
+        let mut events: Vec<ResolvedEvent<'a>> = vec![];
+        let mut keys: Vec<EventKey> = vec![];
+        for (depth, object) in chain.iter().enumerate() {
+            for event in object.event_list.iter() {
+                let key = event.key();
+                if keys.contains(&key) {
+                    continue;
+                }
+
+                let source = if depth == 0 {
+                    match chain[1..]
+                        .iter()
+                        .find(|parent| parent.event_list.iter().any(|e| e.key() == key))
+                    {
+                        Some(parent) => EventSource::Overrides(parent),
+                        None => EventSource::Own,
+                    }
+                } else {
+                    EventSource::Inherited(object)
+                };
+
+                keys.push(key);
+                events.push(ResolvedEvent {
+                    event,
+                    defined_by: object,
+                    source,
+                });
+            }
+        }
+
+        let sprite_id = self.sprite_id.as_ref();
+        let sprite_mask_id = self.sprite_mask_id.as_ref().or(sprite_id);
+        let physics = self.physics_object.then_some(self);
+
+        Ok(ResolvedObject {
+            chain,
+            properties,
+            events,
+            sprite_id,
+            sprite_mask_id,
+            physics,
+        })
+    }
+}
+
+impl ResolvedObject<'_> {
+    /// Returns the property with the given name, if the Object has one.
+    pub fn property(&self, name: &str) -> Option<&ResolvedProperty<'_>> {
+        self.properties
+            .iter()
+            .find(|property| property.definition.common_data.name == name)
+    }
+
+    /// Returns the event with the given key, if the Object runs one.
+    pub fn event(&self, key: &EventKey) -> Option<&ResolvedEvent<'_>> {
+        self.events.iter().find(|event| event.event.key() == *key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::{EventType, ObjectOverrideProperty, Stage};
+    use pretty_assertions::assert_eq;
+
+    fn object(name: &str, parent: Option<&str>) -> Object {
+        Object {
+            common_data: crate::CommonData::new(name.to_string()),
+            parent_object_id: parent.map(|parent| FilesystemPath::new("objects", parent)),
+            ..Default::default()
+        }
+    }
+
+    fn property(name: &str, value: &str) -> ObjectProperty {
+        ObjectProperty {
+            common_data: crate::CommonData::new(name.to_string()),
+            value: value.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn override_property(owner: &str, name: &str, value: &str) -> ObjectOverrideProperty {
+        ObjectOverrideProperty {
+            property_id: Some(FilesystemPath {
+                name: name.to_string(),
+                path: FilesystemPath::new_path("objects", owner),
+            }),
+            object_id: FilesystemPath::new("objects", owner),
+            value: value.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn objects() -> BTreeMap<String, Object> {
+        let mut root = object("par_building", None);
+        root.properties = vec![property("hp", "100"), property("team", "0")];
+        root.event_list = vec![
+            ObjectEvent::new(EventType::Create.into()),
+            ObjectEvent::new(EventType::Step(Stage::Main).into()),
+            ObjectEvent::new(EventKey::collision("obj_bullet")),
+        ];
+        root.sprite_id = Some(FilesystemPath::new("sprites", "spr_building"));
+        root.physics_object = true;
+
+        let mut middle = object("par_house", Some("par_building"));
+        middle.properties = vec![property("rooms", "2")];
+        middle.overridden_properties = vec![override_property("par_building", "hp", "50")];
+        middle.event_list = vec![ObjectEvent::new(EventKey::collision("obj_rocket"))];
+        middle.sprite_mask_id = Some(FilesystemPath::new("sprites", "spr_house_mask"));
+
+        let mut leaf = object("obj_cottage", Some("par_house"));
+        leaf.overridden_properties = vec![override_property("par_house", "rooms", "1")];
+        leaf.event_list = vec![
+            ObjectEvent::new(EventType::Create.into()),
+            ObjectEvent::new(EventType::Destroy.into()),
+        ];
+
+        [root, middle, leaf]
+            .into_iter()
+            .map(|object| (object.common_data.name.clone(), object))
+            .collect()
+    }
+
+    #[test]
+    fn resolve() {
+        let objects = objects();
+        let cottage = &objects["obj_cottage"];
+        let resolved = cottage.resolve(&objects).unwrap();
+
+        let names: Vec<_> = resolved
+            .chain
+            .iter()
+            .map(|object| object.common_data.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["obj_cottage", "par_house", "par_building"]);
+
+        let properties: Vec<_> = resolved
+            .properties
+            .iter()
+            .map(|p| (p.definition.common_data.name.as_str(), p.value))
+            .collect();
+        assert_eq!(
+            properties,
+            vec![("hp", "50"), ("team", "0"), ("rooms", "1")]
+        );
+        assert_eq!(
+            resolved.property("hp").unwrap().overridden_by,
+            Some(&objects["par_house"])
+        );
+        assert_eq!(resolved.property("team").unwrap().overridden_by, None);
+
+        let events: Vec<_> = resolved
+            .events
+            .iter()
+            .map(|e| (e.event.key().filename(), e.source))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                (
+                    "Create_0".to_string(),
+                    EventSource::Overrides(&objects["par_building"])
+                ),
+                ("Destroy_0".to_string(), EventSource::Own),
+                (
+                    "Collision_obj_rocket".to_string(),
+                    EventSource::Inherited(&objects["par_house"])
+                ),
+                (
+                    "Step_0".to_string(),
+                    EventSource::Inherited(&objects["par_building"])
+                ),
+                (
+                    "Collision_obj_bullet".to_string(),
+                    EventSource::Inherited(&objects["par_building"])
+                ),
+            ]
+        );
+
+        // Sprites, masks and physics are never inherited.
+        assert_eq!(resolved.sprite_id, None);
+        assert_eq!(resolved.sprite_mask_id, None);
+        assert_eq!(resolved.physics, None);
+
+        let house = objects["par_house"].resolve(&objects).unwrap();
+        assert_eq!(house.sprite_id, None);
+        assert_eq!(house.sprite_mask_id.unwrap().name, "spr_house_mask");
+        assert_eq!(house.physics, None);
+
+        let building = objects["par_building"].resolve(&objects).unwrap();
+        assert_eq!(building.sprite_id.unwrap().name, "spr_building");
+        assert_eq!(building.sprite_mask_id.unwrap().name, "spr_building");
+        assert_eq!(building.physics, Some(&objects["par_building"]));
+    }
+
+    #[test]
+    fn errors() {
+        let mut objects = objects();
+        objects.get_mut("par_building").unwrap().parent_object_id =
+            Some(FilesystemPath::new("objects", "obj_cottage"));
+        let cottage = &objects["obj_cottage"];
+        assert_eq!(
+            cottage.resolve(&objects).unwrap_err(),
+            ResolveError::Cycle(vec![
+                "obj_cottage".to_string(),
+                "par_house".to_string(),
+                "par_building".to_string(),
+                "obj_cottage".to_string()
+            ])
+        );
+
+        objects.remove("par_building");
+        let cottage = &objects["obj_cottage"];
+        assert_eq!(
+            cottage.resolve(&objects).unwrap_err(),
+            ResolveError::MissingParent {
+                object: "par_house".to_string(),
+                parent: "par_building".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,643 @@
+mod delete;
+pub use delete::*;
+
+use crate::{
+    FilesystemPath, ResourceKind, ResourceNameValidator, ResourceRef, TrailingCommaUtility, Yyp,
+    YypResource,
+};
+use serde_json::Value;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// A GameMaker project on disk: its `yyp` and the directory which holds it.
+///
+/// Changes made through a Project are written to disk immediately. If a
+/// change fails part of the way through, the files it already changed are
+/// restored, and any which could not be are listed in
+/// [`ProjectError::RollbackFailed`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Project {
+    /// The directory holding the `yyp`. Resource paths are relative to this.
+    pub root: PathBuf,
+
+    /// The path of the `yyp` file.
+    pub yyp_path: PathBuf,
+
+    /// The project file.
+    pub yyp: Yyp,
+}
+
+/// Errors which can occur when working with a [`Project`].
+#[derive(Debug, thiserror::Error)]
+pub enum ProjectError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("could not parse `{path}`: {source}")]
+    Parse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[error("`{0}` is not in the project")]
+    MissingResource(ResourceRef),
+    #[error("`{0}` is not a valid resource name")]
+    InvalidName(String),
+    #[error("a resource named `{0}` already exists")]
+    NameTaken(String),
+    #[error("`{0}` already exists")]
+    PathTaken(PathBuf),
+    #[error("{source}, and {} changed files could not be restored", .unrestored.len())]
+    RollbackFailed {
+        source: io::Error,
+        unrestored: Vec<PathBuf>,
+    },
+    #[error("`{resource}` is still used in {} places", .references.len())]
+    InUse {
+        resource: ResourceRef,
+        references: Vec<crate::Reference>,
+    },
+}
+
+impl Project {
+    /// Opens the project whose `yyp` is at `yyp_path`.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the `yyp` cannot be read or parsed.
+    pub fn open(yyp_path: impl AsRef<Path>) -> Result<Self, ProjectError> {
+        let yyp_path = yyp_path.as_ref().to_owned();
+        let root = yyp_path.parent().map(Path::to_owned).unwrap_or_default();
+
+        let text = fs::read_to_string(&yyp_path)?;
+        let yyp = serde_json::from_str(&TrailingCommaUtility::clear_trailing_comma_once(&text))
+            .map_err(|source| ProjectError::Parse {
+                path: yyp_path.clone(),
+                source,
+            })?;
+
+        Ok(Self {
+            root,
+            yyp_path,
+            yyp,
+        })
+    }
+
+    /// Returns the `yyp` entry of the resource, if it is in the project.
+    pub fn resource_id(&self, resource: &ResourceRef) -> Option<&FilesystemPath> {
+        self.yyp
+            .resources
+            .iter()
+            .map(|entry| &entry.id)
+            .find(|id| ResourceRef::from_path(id).as_ref() == Some(resource))
+    }
+
+    /// Renames a resource, along with everything which refers to it.
+    ///
+    /// This renames the resource's directory and `yy` file, and any other
+    /// files in its directory named after it, such as a script's `gml` file.
+    /// Every `yy` file in the project is then searched for references to the
+    /// resource, such as parents, sprites, collision events, tileset
+    /// sprites, room instances and property overrides, which are updated to
+    /// the new name, as are the collision event GML files of objects. Last,
+    /// the resource's entry and any room order entry in the `yyp` are
+    /// updated.
+    ///
+    /// Everything is checked before any file is touched. If writing fails
+    /// part of the way through, the files already changed are restored, as
+    /// far as possible.
+    ///
+    /// # Errors
+    ///
+    /// Errors if `new_name` is not a valid resource name, if any resource
+    /// already has that name, if the resource is not in the project, or if
+    /// any file cannot be read, parsed or written.
+    pub fn rename(&mut self, resource: &ResourceRef, new_name: &str) -> Result<(), ProjectError> {
+        if !ResourceNameValidator::validate_path_once(new_name) {
+            return Err(ProjectError::InvalidName(new_name.to_string()));
+        }
+        let old_id = self
+            .resource_id(resource)
+            .cloned()
+            .ok_or_else(|| ProjectError::MissingResource(resource.clone()))?;
+        if old_id.name == new_name {
+            return Ok(());
+        }
+        if self
+            .yyp
+            .resources
+            .iter()
+            .any(|entry| entry.id != old_id && entry.id.name.eq_ignore_ascii_case(new_name))
+        {
+            return Err(ProjectError::NameTaken(new_name.to_string()));
+        }
+
+        let directory = old_id
+            .path
+            .iter()
+            .next()
+            .and_then(|directory| directory.to_str())
+            .unwrap_or_else(|| resource.kind.directory())
+            .to_string();
+        let new_id = FilesystemPath::new(&directory, new_name);
+
+        let old_dir = self.root.join(&directory).join(&old_id.name);
+        let new_dir = self.root.join(&directory).join(new_name);
+        // a rename which only changes case is the same directory on some
+        // filesystems, so only a different resource counts as taking it.
+        if new_dir.exists() && !old_id.name.eq_ignore_ascii_case(new_name) {
+            return Err(ProjectError::PathTaken(new_dir));
+        }
+
+        let mut plan = Plan::default();
+
+        // the files in the resource's own directory which are named after it.
+        let mut renamed_files = vec![];
+        for entry in fs::read_dir(&old_dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.file_stem().is_some_and(|stem| stem == &*old_id.name) {
+                let file_name = path.file_name().unwrap().to_string_lossy().into_owned();
+                let new_file_name = format!("{}{}", new_name, &file_name[old_id.name.len()..]);
+                plan.renames
+                    .push((path.clone(), old_dir.join(&new_file_name)));
+                renamed_files.push((file_name, new_file_name));
+            }
+        }
+
+        for entry in self.yyp.resources.iter() {
+            let path = self.root.join(&entry.id.path);
+            let mut value = read_value(&path)?;
+            let original = value.clone();
+
+            rename_references(&mut value, &old_id, &new_id);
+            if entry.id == old_id {
+                rename_own(&mut value, &old_id.name, new_name, &renamed_files);
+            }
+            if value != original {
+                plan.writes.push((
+                    path.clone(),
+                    crate::formatter::serialize_untyped_file(&value),
+                ));
+            }
+
+            // collision events are named after the object they collide with.
+            if resource.kind == ResourceKind::Object
+                && ResourceRef::from_path(&entry.id)
+                    .is_some_and(|entry| entry.kind == ResourceKind::Object)
+            {
+                let dir = path.parent().map(Path::to_owned).unwrap_or_default();
+                let from = dir.join(format!("Collision_{}.gml", old_id.name));
+                if from.exists() {
+                    let to = dir.join(format!("Collision_{}.gml", new_name));
+                    if to.exists() {
+                        return Err(ProjectError::PathTaken(to));
+                    }
+                    plan.renames.insert(0, (from, to));
+                }
+            }
+        }
+        plan.renames.push((old_dir, new_dir));
+
+        let mut yyp = self.yyp.clone();
+        for entry in yyp.resources.iter_mut() {
+            if entry.id == old_id {
+                entry.id = new_id.clone();
+            }
+        }
+        yyp.resources.sort_by_cached_key(YypResource::sort_key);
+        for node in yyp.room_order_nodes.iter_mut() {
+            if node.room_id == old_id {
+                node.room_id = new_id.clone();
+            }
+        }
+        plan.writes
+            .push((self.yyp_path.clone(), crate::serialize_file(&yyp)));
+
+        plan.apply()?;
+        self.yyp = yyp;
+
+        Ok(())
+    }
+}
+
+/// The file changes which make up one change to a project. Files are written
+/// before anything is renamed or removed, so writes use the paths from before
+/// the change.
+#[derive(Debug, Default)]
+pub(crate) struct Plan {
+    pub writes: Vec<(PathBuf, String)>,
+    pub renames: Vec<(PathBuf, PathBuf)>,
+    /// Files and directories to remove. These are moved aside while the plan
+    /// runs, and only removed once every other change has been made.
+    pub removals: Vec<PathBuf>,
+}
+
+enum Undo {
+    Write(PathBuf, Vec<u8>),
+    Rename(PathBuf, PathBuf),
+}
+
+impl Plan {
+    /// Makes every change in the plan. If any change fails, the changes
+    /// already made are undone, as far as possible, and the error returned.
+    ///
+    /// Once every change has been made, the removed files are deleted. This
+    /// is best-effort: a file which cannot be deleted is left behind under
+    /// its moved-aside name, and the plan still counts as applied.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`ProjectError::Io`] if a change fails and everything was
+    /// undone, or with [`ProjectError::RollbackFailed`] if some changes could
+    /// not be undone.
+    pub fn apply(self) -> Result<(), ProjectError> {
+        let mut undo = vec![];
+        let mut trash = vec![];
+
+        let mut run = || -> io::Result<()> {
+            for (path, contents) in self.writes.iter() {
+                let original = fs::read(path)?;
+                fs::write(path, contents)?;
+                undo.push(Undo::Write(path.clone(), original));
+            }
+            for (from, to) in self.renames.iter() {
+                fs::rename(from, to)?;
+                undo.push(Undo::Rename(from.clone(), to.clone()));
+            }
+            for path in self.removals.iter() {
+                let mut file_name = std::ffi::OsString::from(".");
+                file_name.push(path.file_name().unwrap_or_default());
+                file_name.push(".deleted");
+                let to = path.with_file_name(file_name);
+
+                fs::rename(path, &to)?;
+                undo.push(Undo::Rename(path.clone(), to.clone()));
+                trash.push(to);
+            }
+
+            Ok(())
+        };
+
+        if let Err(source) = run() {
+            let mut unrestored = vec![];
+            for step in undo.into_iter().rev() {
+                match step {
+                    Undo::Write(path, original) => {
+                        if fs::write(&path, original).is_err() {
+                            unrestored.push(path);
+                        }
+                    }
+                    Undo::Rename(from, to) => {
+                        if fs::rename(&to, &from).is_err() {
+                            unrestored.push(from);
+                        }
+                    }
+                }
+            }
+
+            return Err(if unrestored.is_empty() {
+                ProjectError::Io(source)
+            } else {
+                ProjectError::RollbackFailed { source, unrestored }
+            });
+        }
+
+        for path in trash {
+            let _ = if path.is_dir() {
+                fs::remove_dir_all(path)
+            } else {
+                fs::remove_file(path)
+            };
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads a `yy` file as JSON.
+pub(crate) fn read_value(path: &Path) -> Result<Value, ProjectError> {
+    let text = fs::read_to_string(path)?;
+
+    serde_json::from_str(&TrailingCommaUtility::clear_trailing_comma_once(&text)).map_err(
+        |source| ProjectError::Parse {
+            path: path.to_owned(),
+            source,
+        },
+    )
+}
+
+/// Updates every reference to `old` within `value`. References are objects
+/// with a `name` and a `path`, where the `path` is the resource's `yy` file.
+/// Some references, such as property overrides, use the `name` for something
+/// within the resource, so the `name` is only changed if it is the resource's.
+fn rename_references(value: &mut Value, old: &FilesystemPath, new: &FilesystemPath) {
+    match value {
+        Value::Object(map) => {
+            let is_reference = map.get("path").and_then(Value::as_str) == old.path.to_str()
+                && map.get("name").is_some_and(Value::is_string);
+
+            if is_reference {
+                map.insert(
+                    "path".into(),
+                    new.path.to_string_lossy().into_owned().into(),
+                );
+                if map.get("name").and_then(Value::as_str) == Some(&old.name) {
+                    map.insert("name".into(), new.name.clone().into());
+                }
+            } else {
+                for value in map.values_mut() {
+                    rename_references(value, old, new);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                rename_references(value, old, new);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Renames the resource within its own `yy` file: the `name` of it and of any
+/// typed data named after it, such as a sprite's sequence, and any mention of
+/// the files renamed alongside it.
+fn rename_own(value: &mut Value, old: &str, new: &str, renamed_files: &[(String, String)]) {
+    match value {
+        Value::Object(map) => {
+            if map.contains_key("resourceType")
+                && map.get("name").and_then(Value::as_str) == Some(old)
+            {
+                map.insert("name".into(), new.into());
+                if map.contains_key("%Name") {
+                    map.insert("%Name".into(), new.into());
+                }
+            }
+
+            for value in map.values_mut() {
+                rename_own(value, old, new, renamed_files);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                rename_own(value, old, new, renamed_files);
+            }
+        }
+        Value::String(text) => {
+            if let Some((_, new_file)) = renamed_files.iter().find(|(file, _)| file == text) {
+                *text = new_file.clone();
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        EventKey, EventType, Instance, Instances, LayerData, Object, ObjectBuilder, Room,
+        RoomLayer, RoomOrderId, ViewPath, ViewPathLocation,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn folder() -> ViewPath {
+        ViewPath {
+            name: "Objects".to_string(),
+            path: ViewPathLocation("folders/Objects.yy".to_string()),
+        }
+    }
+
+    fn read<T: serde::de::DeserializeOwned>(path: &Path) -> T {
+        let text = fs::read_to_string(path).unwrap();
+        serde_json::from_str(&TrailingCommaUtility::clear_trailing_comma_once(&text)).unwrap()
+    }
+
+    /// Writes a small project with two objects, one colliding with the
+    /// other, and a room with an instance of each.
+    pub(super) fn write_project(root: &Path) -> Project {
+        let objects = root.join("objects");
+        let wall = ObjectBuilder::new("obj_wall", folder())
+            .event(EventType::Create)
+            .event(EventKey::collision("obj_wall"))
+            .write(&objects)
+            .unwrap();
+        ObjectBuilder::new("obj_player", folder())
+            .event(EventType::Create)
+            .event_with_code(EventKey::collision("obj_wall"), "speed = 0;\n")
+            .write(&objects)
+            .unwrap();
+        ObjectBuilder::new("obj_brick", folder())
+            .parent(&wall)
+            .write(&objects)
+            .unwrap();
+
+        let instance = |name: &str, object: &str| Instance {
+            name: name.to_string(),
+            object_id: FilesystemPath::new("objects", object),
+            ..Default::default()
+        };
+        let room = Room {
+            common_data: crate::CommonData::new("rm_level".to_string()),
+            layers: vec![RoomLayer {
+                data: LayerData::Instance(Instances {
+                    instances: vec![
+                        instance("inst_player", "obj_player"),
+                        instance("inst_wall", "obj_wall"),
+                    ],
+                }),
+                visible: true,
+                depth: 0,
+                user_defined_depth: false,
+                grid_x: 32,
+                grid_y: 32,
+                layers: vec![],
+                hierarchy_frozen: false,
+                resource_version: Default::default(),
+                name: "Instances".to_string(),
+            }],
+            ..Default::default()
+        };
+        fs::create_dir_all(root.join("rooms/rm_level")).unwrap();
+        fs::write(
+            root.join("rooms/rm_level/rm_level.yy"),
+            crate::serialize_file(&room),
+        )
+        .unwrap();
+
+        let mut yyp = Yyp::default();
+        yyp.common_data.name = "game".to_string();
+        yyp.resources = [
+            ("objects", "obj_brick"),
+            ("objects", "obj_player"),
+            ("objects", "obj_wall"),
+            ("rooms", "rm_level"),
+        ]
+        .iter()
+        .map(|(base, name)| YypResource {
+            id: FilesystemPath::new(base, name),
+        })
+        .collect();
+        yyp.room_order_nodes = vec![RoomOrderId {
+            room_id: FilesystemPath::new("rooms", "rm_level"),
+        }];
+        fs::write(root.join("game.yyp"), crate::serialize_file(&yyp)).unwrap();
+
+        Project::open(root.join("game.yyp")).unwrap()
+    }
+
+    #[test]
+    fn rename_object() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let mut project = write_project(root);
+
+        let wall = ResourceRef::new(ResourceKind::Object, "obj_wall");
+        project.rename(&wall, "obj_block").unwrap();
+
+        assert!(!root.join("objects/obj_wall").exists());
+        let block: Object = read(&root.join("objects/obj_block/obj_block.yy"));
+        assert_eq!(block.common_data.name, "obj_block");
+        assert_eq!(
+            block.event_list[1].collision_object_id,
+            Some(FilesystemPath::new("objects", "obj_block"))
+        );
+        assert!(
+            block
+                .check_event_files(&root.join("objects/obj_block"))
+                .unwrap()
+                .is_empty()
+        );
+
+        let player: Object = read(&root.join("objects/obj_player/obj_player.yy"));
+        assert_eq!(
+            player.event_list[1].collision_object_id,
+            Some(FilesystemPath::new("objects", "obj_block"))
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("objects/obj_player/Collision_obj_block.gml")).unwrap(),
+            "speed = 0;\n"
+        );
+
+        let brick: Object = read(&root.join("objects/obj_brick/obj_brick.yy"));
+        assert_eq!(
+            brick.parent_object_id,
+            Some(FilesystemPath::new("objects", "obj_block"))
+        );
+
+        let room: Room = read(&root.join("rooms/rm_level/rm_level.yy"));
+        assert_eq!(room.instances()[1].1.object_id.name, "obj_block");
+
+        let yyp: Yyp = read(&root.join("game.yyp"));
+        assert_eq!(yyp, project.yyp);
+        assert!(
+            project
+                .resource_id(&ResourceRef::new(ResourceKind::Object, "obj_block"))
+                .is_some()
+        );
+        assert!(project.resource_id(&wall).is_none());
+
+        // room order follows renamed rooms.
+        let level = ResourceRef::new(ResourceKind::Room, "rm_level");
+        project.rename(&level, "rm_start").unwrap();
+        assert_eq!(
+            project.yyp.room_order_nodes[0].room_id,
+            FilesystemPath::new("rooms", "rm_start")
+        );
+        assert!(root.join("rooms/rm_start/rm_start.yy").exists());
+    }
+
+    #[test]
+    fn rename_sprite() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let mut project = write_project(root);
+
+        let sprite = include_str!("../data/formatting/sprite_one.yy")
+            .replace("spr_manor_doorway2_spring", "spr_collision_tile_info");
+        fs::create_dir_all(root.join("sprites/spr_collision_tile_info")).unwrap();
+        fs::write(
+            root.join("sprites/spr_collision_tile_info/spr_collision_tile_info.yy"),
+            &sprite,
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("tilesets/tile_collision_info")).unwrap();
+        fs::write(
+            root.join("tilesets/tile_collision_info/tile_collision_info.yy"),
+            include_str!("../data/tileset/test.yy"),
+        )
+        .unwrap();
+
+        project.yyp.resources.extend([
+            YypResource {
+                id: FilesystemPath::new("sprites", "spr_collision_tile_info"),
+            },
+            YypResource {
+                id: FilesystemPath::new("tilesets", "tile_collision_info"),
+            },
+        ]);
+        fs::write(&project.yyp_path, crate::serialize_file(&project.yyp)).unwrap();
+
+        let old = ResourceRef::new(ResourceKind::Sprite, "spr_collision_tile_info");
+        project.rename(&old, "spr_tile_info").unwrap();
+
+        // the sprite keeps its layout, with only its name changed.
+        assert_eq!(
+            fs::read_to_string(root.join("sprites/spr_tile_info/spr_tile_info.yy")).unwrap(),
+            sprite.replace("spr_collision_tile_info", "spr_tile_info")
+        );
+        let tileset =
+            read_value(&root.join("tilesets/tile_collision_info/tile_collision_info.yy")).unwrap();
+        assert_eq!(tileset["spriteId"]["name"], "spr_tile_info");
+        assert_eq!(
+            tileset["spriteId"]["path"],
+            "sprites/spr_tile_info/spr_tile_info.yy"
+        );
+
+        // sprites which the typings cannot parse are still renamed, and keep
+        // the fields the typings do not know.
+        let path = root.join("sprites/spr_tile_info/spr_tile_info.yy");
+        let mut value = read_value(&path).unwrap();
+        value["width"] = "wide".into();
+        value["futureField"] = true.into();
+        assert!(serde_json::from_value::<crate::Sprite>(value.clone()).is_err());
+        fs::write(&path, crate::serialize_file(&value)).unwrap();
+
+        let tile_info = ResourceRef::new(ResourceKind::Sprite, "spr_tile_info");
+        project.rename(&tile_info, "spr_tiles").unwrap();
+        let value = read_value(&root.join("sprites/spr_tiles/spr_tiles.yy")).unwrap();
+        assert_eq!(value["name"], "spr_tiles");
+        assert_eq!(value["width"], "wide");
+        assert_eq!(value["futureField"], true);
+    }
+
+    #[test]
+    fn rename_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut project = write_project(dir.path());
+        let wall = ResourceRef::new(ResourceKind::Object, "obj_wall");
+
+        assert!(matches!(
+            project.rename(&wall, "9lives"),
+            Err(ProjectError::InvalidName(_))
+        ));
+        assert!(matches!(
+            project.rename(&wall, "OBJ_PLAYER"),
+            Err(ProjectError::NameTaken(_))
+        ));
+        assert!(matches!(
+            project.rename(
+                &ResourceRef::new(ResourceKind::Object, "obj_nothing"),
+                "obj_a"
+            ),
+            Err(ProjectError::MissingResource(_))
+        ));
+
+        fs::create_dir(dir.path().join("objects/obj_stray")).unwrap();
+        assert!(matches!(
+            project.rename(&wall, "obj_stray"),
+            Err(ProjectError::PathTaken(_))
+        ));
+        assert!(dir.path().join("objects/obj_wall/obj_wall.yy").exists());
+    }
+}
@@ -0,0 +1,449 @@
+use super::{Plan, Project, ProjectError, read_value};
+use crate::{FilesystemPath, ObjectEvent, Reference, ReferenceGraph, ResourceKind, ResourceRef};
+use serde_json::Value;
+use std::{collections::BTreeSet, path::Path};
+
+/// What [`Project::delete`] does with references to the deleted resource.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub enum DeleteMode {
+    /// Does not delete a resource which anything else refers to.
+    #[default]
+    Refuse,
+
+    /// Clears every reference to the resource, such as setting an Object's
+    /// `sprite_id` to `None`. References which cannot be cleared are removed
+    /// along with what holds them: collision events, with their GML files,
+    /// room instances, room assets, and property overrides.
+    NullReferences,
+
+    /// Deletes every resource which refers to the resource as well, and
+    /// every resource which refers to those, and so on.
+    Cascade,
+}
+
+impl Project {
+    /// Reads every resource in the project into a [`ReferenceGraph`].
+    ///
+    /// Objects, Sprites, Sounds, TileSets and Rooms are parsed into their
+    /// typings. Every other resource, and any of those which its typings
+    /// cannot parse, has its references collected from its plain JSON with
+    /// [`ReferenceGraph::add_value`] instead, so one unusual file does not
+    /// hide its references or stop the graph being built.
+    ///
+    /// # Errors
+    ///
+    /// Errors if any resource cannot be read, or is not valid JSON.
+    pub fn reference_graph(&self) -> Result<ReferenceGraph, ProjectError> {
+        fn parse<T: serde::de::DeserializeOwned>(value: &Value) -> Option<T> {
+            serde_json::from_value(value.clone()).ok()
+        }
+
+        let mut graph = ReferenceGraph::new();
+        graph.add_yyp(&self.yyp);
+
+        for entry in self.yyp.resources.iter() {
+            let Some(resource) = ResourceRef::from_path(&entry.id) else {
+                continue;
+            };
+            let value = read_value(&self.root.join(&entry.id.path))?;
+
+            let added = match resource.kind {
+                ResourceKind::Object => parse(&value).map(|object| graph.add_object(&object)),
+                ResourceKind::Sprite => parse(&value).map(|sprite| graph.add_sprite(&sprite)),
+                ResourceKind::Sound => parse(&value).map(|sound| graph.add_sound(&sound)),
+                ResourceKind::TileSet => parse(&value).map(|tileset| graph.add_tileset(&tileset)),
+                ResourceKind::Room => parse(&value).map(|room| graph.add_room(&room)),
+                _ => None,
+            };
+            if added.is_none() {
+                graph.add_value(resource, &value);
+            }
+        }
+
+        Ok(graph)
+    }
+
+    /// Deletes a resource: its directory, its entry in the `yyp`, and its
+    /// place in the room order if it is a room. What happens to references to
+    /// the resource depends on `mode`; references from the resource to
+    /// itself, and from the room order, never stop it being deleted.
+    ///
+    /// Returns every resource deleted, which is more than `resource` in
+    /// [`DeleteMode::Cascade`]. As with [`rename`](Self::rename), a delete
+    /// which fails part of the way through restores the files it changed, as
+    /// far as possible. The deleted files are removed last, and any which
+    /// cannot be are left behind as hidden `.deleted` files.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the resource is not in the project, if it is still used in
+    /// [`DeleteMode::Refuse`], or if any file cannot be read, parsed as JSON,
+    /// or written.
+    pub fn delete(
+        &mut self,
+        resource: &ResourceRef,
+        mode: DeleteMode,
+    ) -> Result<Vec<ResourceRef>, ProjectError> {
+        if self.resource_id(resource).is_none() {
+            return Err(ProjectError::MissingResource(resource.clone()));
+        }
+        let graph = self.reference_graph()?;
+
+        let users = |resource: &ResourceRef| -> Vec<Reference> {
+            graph
+                .used_by(resource)
+                .into_iter()
+                .filter(|reference| {
+                    reference.from != *resource && reference.from.kind != ResourceKind::Project
+                })
+                .cloned()
+                .collect()
+        };
+
+        let mut deleted = vec![resource.clone()];
+        match mode {
+            DeleteMode::Refuse => {
+                let references = users(resource);
+                if !references.is_empty() {
+                    return Err(ProjectError::InUse {
+                        resource: resource.clone(),
+                        references,
+                    });
+                }
+            }
+            DeleteMode::NullReferences => {}
+            DeleteMode::Cascade => {
+                let mut index = 0;
+                while index < deleted.len() {
+                    for reference in users(&deleted[index]) {
+                        if !deleted.contains(&reference.from) {
+                            deleted.push(reference.from);
+                        }
+                    }
+                    index += 1;
+                }
+            }
+        }
+
+        let deleted_ids: Vec<FilesystemPath> = deleted
+            .iter()
+            .filter_map(|resource| self.resource_id(resource).cloned())
+            .collect();
+
+        let mut plan = Plan::default();
+        for entry in self.yyp.resources.iter() {
+            let path = self.root.join(&entry.id.path);
+            if deleted_ids.contains(&entry.id) {
+                plan.removals
+                    .push(path.parent().map(Path::to_owned).unwrap_or_default());
+                continue;
+            }
+
+            let mut value = read_value(&path)?;
+            let original = value.clone();
+            let mut removed = vec![];
+            remove_references(&mut value, &deleted_ids, &mut removed);
+
+            let removed_instances: BTreeSet<&str> = removed
+                .iter()
+                .filter(|value| value["resourceType"] == "GMRInstance")
+                .filter_map(|value| value["name"].as_str())
+                .collect();
+            if let Some(Value::Array(order)) = value.get_mut("instanceCreationOrder") {
+                order.retain(|instance| {
+                    !instance["name"]
+                        .as_str()
+                        .is_some_and(|name| removed_instances.contains(name))
+                });
+            }
+
+            for event in removed
+                .iter()
+                .filter(|value| value["resourceType"] == "GMEvent")
+            {
+                let event: ObjectEvent =
+                    serde_json::from_value(event.clone()).map_err(|source| {
+                        ProjectError::Parse {
+                            path: path.clone(),
+                            source,
+                        }
+                    })?;
+                let gml = path.with_file_name(format!("{}.gml", event.filename()));
+                if gml.exists() {
+                    plan.removals.push(gml);
+                }
+            }
+
+            if value != original {
+                plan.writes
+                    .push((path, crate::formatter::serialize_untyped_file(&value)));
+            }
+        }
+
+        let mut yyp = self.yyp.clone();
+        yyp.resources
+            .retain(|entry| !deleted_ids.contains(&entry.id));
+        yyp.room_order_nodes
+            .retain(|node| !deleted_ids.contains(&node.room_id));
+        plan.writes
+            .push((self.yyp_path.clone(), crate::serialize_file(&yyp)));
+
+        plan.apply()?;
+        self.yyp = yyp;
+
+        Ok(deleted)
+    }
+}
+
+/// Whether `value` is a reference to one of `deleted`.
+fn is_reference(value: &Value, deleted: &[FilesystemPath]) -> bool {
+    value.get("name").is_some_and(Value::is_string)
+        && value["path"]
+            .as_str()
+            .is_some_and(|path| deleted.iter().any(|id| id.path == Path::new(path)))
+}
+
+/// Whether `value` cannot exist without one of `deleted`, either because it
+/// is a reference to it, or because it is typed data which is meaningless
+/// without it.
+fn requires_deleted(value: &Value, deleted: &[FilesystemPath]) -> bool {
+    let fields: &[&str] = match value["resourceType"].as_str() {
+        Some("GMEvent") => &["collisionObjectId"],
+        Some("GMRInstance") => &["objectId"],
+        Some("GMRSpriteGraphic") => &["spriteId"],
+        Some("GMRSequenceGraphicElement") => &["sequenceId"],
+        Some("GMOverriddenProperty") => &["objectId", "propertyId"],
+        _ => &[],
+    };
+
+    is_reference(value, deleted)
+        || fields
+            .iter()
+            .any(|field| is_reference(&value[*field], deleted))
+}
+
+/// Clears every reference to `deleted` within `value`, removing anything in
+/// an array which requires a deleted resource. Removed values are added to
+/// `removed`.
+fn remove_references(value: &mut Value, deleted: &[FilesystemPath], removed: &mut Vec<Value>) {
+    match value {
+        Value::Object(map) => {
+            for value in map.values_mut() {
+                if is_reference(value, deleted) {
+                    *value = Value::Null;
+                } else {
+                    remove_references(value, deleted, removed);
+                }
+            }
+        }
+        Value::Array(values) => {
+            let (gone, kept) = std::mem::take(values)
+                .into_iter()
+                .partition(|value| requires_deleted(value, deleted));
+            *values = kept;
+            removed.extend::<Vec<_>>(gone);
+
+            for value in values {
+                remove_references(value, deleted, removed);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Object, ReferenceLocation, Room, TrailingCommaUtility, Yyp, project::tests::write_project,
+    };
+    use pretty_assertions::assert_eq;
+    use std::fs;
+
+    fn read<T: serde::de::DeserializeOwned>(path: &Path) -> T {
+        let text = fs::read_to_string(path).unwrap();
+        serde_json::from_str(&TrailingCommaUtility::clear_trailing_comma_once(&text)).unwrap()
+    }
+
+    #[test]
+    fn refuse() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut project = write_project(dir.path());
+        let wall = ResourceRef::new(ResourceKind::Object, "obj_wall");
+
+        let Err(ProjectError::InUse { references, .. }) = project.delete(&wall, DeleteMode::Refuse)
+        else {
+            panic!("obj_wall is in use");
+        };
+        let users: Vec<_> = references
+            .iter()
+            .map(|reference| reference.from.name.as_str())
+            .collect();
+        assert_eq!(users, vec!["obj_brick", "obj_player", "rm_level"]);
+        assert!(dir.path().join("objects/obj_wall").exists());
+
+        // only the room order refers to the room.
+        let level = ResourceRef::new(ResourceKind::Room, "rm_level");
+        assert_eq!(
+            project.delete(&level, DeleteMode::Refuse).unwrap(),
+            vec![level]
+        );
+        assert!(!dir.path().join("rooms").join("rm_level").exists());
+        assert!(project.yyp.room_order_nodes.is_empty());
+        assert_eq!(project.yyp.resources.len(), 3);
+    }
+
+    #[test]
+    fn null_references() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let mut project = write_project(root);
+        let wall = ResourceRef::new(ResourceKind::Object, "obj_wall");
+
+        project.delete(&wall, DeleteMode::NullReferences).unwrap();
+        assert!(!root.join("objects/obj_wall").exists());
+
+        let brick: Object = read(&root.join("objects/obj_brick/obj_brick.yy"));
+        assert_eq!(brick.parent_object_id, None);
+
+        let player: Object = read(&root.join("objects/obj_player/obj_player.yy"));
+        assert_eq!(player.event_list.len(), 1);
+        assert!(
+            !root
+                .join("objects/obj_player/Collision_obj_wall.gml")
+                .exists()
+        );
+        assert!(
+            player
+                .check_event_files(&root.join("objects/obj_player"))
+                .unwrap()
+                .is_empty()
+        );
+
+        let room: Room = read(&root.join("rooms/rm_level/rm_level.yy"));
+        let instances: Vec<_> = room
+            .instances()
+            .into_iter()
+            .map(|(_, instance)| instance.name.as_str())
+            .collect();
+        assert_eq!(instances, vec!["inst_player"]);
+
+        let yyp: Yyp = read(&root.join("game.yyp"));
+        assert_eq!(yyp, project.yyp);
+        assert!(project.resource_id(&wall).is_none());
+        assert!(project.reference_graph().unwrap().dangling().is_empty());
+    }
+
+    #[test]
+    fn cascade() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut project = write_project(dir.path());
+        let player = ResourceRef::new(ResourceKind::Object, "obj_player");
+
+        let deleted = project.delete(&player, DeleteMode::Cascade).unwrap();
+        assert_eq!(
+            deleted,
+            vec![player, ResourceRef::new(ResourceKind::Room, "rm_level")]
+        );
+        assert!(!dir.path().join("rooms/rm_level").exists());
+        assert!(dir.path().join("objects/obj_wall").exists());
+        assert!(project.yyp.room_order_nodes.is_empty());
+
+        let names: Vec<_> = project
+            .yyp
+            .resources
+            .iter()
+            .map(|entry| entry.id.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["obj_brick", "obj_wall"]);
+
+        let leftovers: Vec<_> = fs::read_dir(dir.path().join("objects"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(leftovers.len(), 2);
+    }
+
+    #[test]
+    fn unparseable_resources() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let mut project = write_project(root);
+
+        // an instance without a position cannot be parsed as a Room.
+        let room_path = root.join("rooms/rm_level/rm_level.yy");
+        let mut room = read_value(&room_path).unwrap();
+        room["layers"][0]["instances"][1]
+            .as_object_mut()
+            .unwrap()
+            .remove("x");
+        fs::write(&room_path, serde_json::to_string(&room).unwrap()).unwrap();
+        assert!(serde_json::from_value::<Room>(room).is_err());
+
+        let wall = ResourceRef::new(ResourceKind::Object, "obj_wall");
+        let Err(ProjectError::InUse { references, .. }) = project.delete(&wall, DeleteMode::Refuse)
+        else {
+            panic!("obj_wall is in use");
+        };
+        assert_eq!(
+            references.last().unwrap().location,
+            ReferenceLocation::Field("/layers/0/instances/1/objectId".to_string())
+        );
+
+        project.delete(&wall, DeleteMode::NullReferences).unwrap();
+        let room = read_value(&room_path).unwrap();
+        assert_eq!(room["layers"][0]["instances"].as_array().unwrap().len(), 1);
+        assert!(project.reference_graph().unwrap().dangling().is_empty());
+    }
+
+    #[test]
+    fn room_sequences() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let mut project = write_project(root);
+
+        let room_path = root.join("rooms/rm_overworld/rm_overworld.yy");
+        fs::create_dir_all(room_path.parent().unwrap()).unwrap();
+        fs::write(&room_path, include_str!("../../data/rooms/rm_overworld.yy")).unwrap();
+        fs::create_dir_all(root.join("sequences/seq_waterfall")).unwrap();
+        fs::write(
+            root.join("sequences/seq_waterfall/seq_waterfall.yy"),
+            r#"{"name":"seq_waterfall","resourceType":"GMSequence","resourceVersion":"1.4"}"#,
+        )
+        .unwrap();
+
+        project.yyp.resources.extend([
+            crate::YypResource {
+                id: FilesystemPath::new("rooms", "rm_overworld"),
+            },
+            crate::YypResource {
+                id: FilesystemPath::new("sequences", "seq_waterfall"),
+            },
+        ]);
+        fs::write(&project.yyp_path, crate::serialize_file(&project.yyp)).unwrap();
+
+        let waterfall = ResourceRef::new(ResourceKind::Sequence, "seq_waterfall");
+        project
+            .delete(&waterfall, DeleteMode::NullReferences)
+            .unwrap();
+        assert!(!root.join("sequences/seq_waterfall").exists());
+
+        // the sequence's element is removed, not left with a null sequence.
+        let room = read_value(&room_path).unwrap();
+        let assets: Vec<_> = room["layers"][3]["assets"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|asset| asset["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(assets, vec!["graphic_6B1E3F20", "particle_4E2D7B15"]);
+        assert!(
+            project
+                .reference_graph()
+                .unwrap()
+                .dangling()
+                .iter()
+                .all(|reference| reference.to != waterfall)
+        );
+    }
+}
@@ -0,0 +1,755 @@
+use crate::{
+    AssetLayerItem, FilesystemPath, LayerData, Object, Room, RoomLayer, Sound, Sprite, TileSet, Yyp,
+};
+use serde_json::Value;
+use std::{collections::BTreeSet, fmt};
+
+/// The kinds of resource which can refer to, or be referred to by, another
+/// resource.
+#[derive(Debug, PartialEq, Eq, Hash, Ord, PartialOrd, Copy, Clone)]
+pub enum ResourceKind {
+    AnimationCurve,
+    AudioGroup,
+    Extension,
+    Font,
+    Note,
+    Object,
+    Path,
+    /// The project's `yyp` itself, which refers to rooms in its room order.
+    Project,
+    Room,
+    Script,
+    Sequence,
+    Shader,
+    Sound,
+    Sprite,
+    TextureGroup,
+    TileSet,
+    Timeline,
+}
+
+impl ResourceKind {
+    /// The first component of the path of a resource of this kind, such as
+    /// `objects` in `objects/obj_player/obj_player.yy`. Texture and audio
+    /// groups use their `texturegroups` and `audiogroups` prefixes.
+    pub fn directory(&self) -> &'static str {
+        match self {
+            ResourceKind::AnimationCurve => "animcurves",
+            ResourceKind::AudioGroup => "audiogroups",
+            ResourceKind::Extension => "extensions",
+            ResourceKind::Font => "fonts",
+            ResourceKind::Note => "notes",
+            ResourceKind::Object => "objects",
+            ResourceKind::Path => "paths",
+            ResourceKind::Project => "project",
+            ResourceKind::Room => "rooms",
+            ResourceKind::Script => "scripts",
+            ResourceKind::Sequence => "sequences",
+            ResourceKind::Shader => "shaders",
+            ResourceKind::Sound => "sounds",
+            ResourceKind::Sprite => "sprites",
+            ResourceKind::TextureGroup => "texturegroups",
+            ResourceKind::TileSet => "tilesets",
+            ResourceKind::Timeline => "timelines",
+        }
+    }
+
+    /// Finds the kind of resource stored in the given directory. This is the
+    /// reverse of [`directory`](Self::directory).
+    pub fn from_directory(directory: &str) -> Option<Self> {
+        const ALL: [ResourceKind; 17] = [
+            ResourceKind::AnimationCurve,
+            ResourceKind::AudioGroup,
+            ResourceKind::Extension,
+            ResourceKind::Font,
+            ResourceKind::Note,
+            ResourceKind::Object,
+            ResourceKind::Path,
+            ResourceKind::Project,
+            ResourceKind::Room,
+            ResourceKind::Script,
+            ResourceKind::Sequence,
+            ResourceKind::Shader,
+            ResourceKind::Sound,
+            ResourceKind::Sprite,
+            ResourceKind::TextureGroup,
+            ResourceKind::TileSet,
+            ResourceKind::Timeline,
+        ];
+
+        ALL.into_iter().find(|kind| kind.directory() == directory)
+    }
+}
+
+/// A resource, named by its kind and name.
+#[derive(Debug, PartialEq, Eq, Hash, Ord, PartialOrd, Clone)]
+pub struct ResourceRef {
+    pub kind: ResourceKind,
+    pub name: String,
+}
+
+impl ResourceRef {
+    /// Creates a new ResourceRef.
+    pub fn new(kind: ResourceKind, name: &str) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+        }
+    }
+
+    /// Creates a ResourceRef from the path of a resource, such as a
+    /// `YypResource`'s `id`. Returns `None` if the path is not in a known
+    /// resource directory.
+    pub fn from_path(path: &FilesystemPath) -> Option<Self> {
+        let directory = path.path.iter().next()?.to_str()?;
+
+        ResourceKind::from_directory(directory).map(|kind| Self::new(kind, &path.name))
+    }
+}
+
+impl fmt::Display for ResourceRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.kind.directory(), self.name)
+    }
+}
+
+/// Where in a resource a [`Reference`] is made.
+#[derive(Debug, PartialEq, Eq, Hash, Ord, PartialOrd, Clone)]
+pub enum ReferenceLocation {
+    /// `Object::sprite_id`.
+    ObjectSprite,
+    /// `Object::sprite_mask_id`.
+    ObjectSpriteMask,
+    /// `Object::parent_object_id`.
+    ObjectParent,
+    /// The `collision_object_id` of the event at this index of
+    /// `Object::event_list`.
+    CollisionEvent(usize),
+    /// `Sprite::texture_group_id`.
+    SpriteTextureGroup,
+    /// `TileSet::sprite_id`.
+    TileSetSprite,
+    /// `TileSet::texture_group_id`.
+    TileSetTextureGroup,
+    /// `Sound::audio_group_id`.
+    SoundAudioGroup,
+    /// `Room::parent_room`.
+    RoomParent,
+    /// The object of an instance placed in a room.
+    RoomInstance { layer: String, instance: String },
+    /// The tileset of a tile layer.
+    RoomTilemap { layer: String },
+    /// The sprite of a background layer.
+    RoomBackground { layer: String },
+    /// The sprite or sequence of the asset at this index of an asset layer.
+    RoomAsset { layer: String, index: usize },
+    /// The path of a path layer.
+    RoomPath { layer: String },
+    /// The object followed by the view at this index of `Room::views`.
+    RoomView(usize),
+    /// The room at this index of `Yyp::room_order_nodes`.
+    RoomOrder(usize),
+    /// A field of a resource which was read as plain JSON, given as a JSON
+    /// pointer, such as `/layers/0/instances/1/objectId`.
+    Field(String),
+}
+
+impl fmt::Display for ReferenceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReferenceLocation::ObjectSprite => write!(f, "sprite"),
+            ReferenceLocation::ObjectSpriteMask => write!(f, "collision mask"),
+            ReferenceLocation::ObjectParent => write!(f, "parent object"),
+            ReferenceLocation::CollisionEvent(index) => write!(f, "collision event {}", index),
+            ReferenceLocation::SpriteTextureGroup | ReferenceLocation::TileSetTextureGroup => {
+                write!(f, "texture group")
+            }
+            ReferenceLocation::TileSetSprite => write!(f, "sprite"),
+            ReferenceLocation::SoundAudioGroup => write!(f, "audio group"),
+            ReferenceLocation::RoomParent => write!(f, "parent room"),
+            ReferenceLocation::RoomInstance { layer, instance } => {
+                write!(f, "instance `{}` on layer `{}`", instance, layer)
+            }
+            ReferenceLocation::RoomTilemap { layer } => write!(f, "tile layer `{}`", layer),
+            ReferenceLocation::RoomBackground { layer } => {
+                write!(f, "background layer `{}`", layer)
+            }
+            ReferenceLocation::RoomAsset { layer, index } => {
+                write!(f, "asset {} on layer `{}`", index, layer)
+            }
+            ReferenceLocation::RoomPath { layer } => write!(f, "path layer `{}`", layer),
+            ReferenceLocation::RoomView(index) => write!(f, "view {}", index),
+            ReferenceLocation::RoomOrder(index) => write!(f, "room order {}", index),
+            ReferenceLocation::Field(pointer) => write!(f, "field `{}`", pointer),
+        }
+    }
+}
+
+/// One resource referring to another.
+#[derive(Debug, PartialEq, Eq, Hash, Ord, PartialOrd, Clone)]
+pub struct Reference {
+    /// The resource which holds the reference.
+    pub from: ResourceRef,
+
+    /// The resource referred to.
+    pub to: ResourceRef,
+
+    /// Where in `from` the reference is made.
+    pub location: ReferenceLocation,
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}) -> {}", self.from, self.location, self.to)
+    }
+}
+
+/// Every reference between the resources of a project.
+///
+/// Resources are added one at a time, which records that the resource
+/// exists along with the references it makes. Adding a [`Yyp`] records every
+/// resource, texture group and audio group it lists, so that references to
+/// resources which have not been added themselves are not dangling.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct ReferenceGraph {
+    resources: BTreeSet<ResourceRef>,
+    references: Vec<Reference>,
+}
+
+impl ReferenceGraph {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that a resource exists, without any references.
+    pub fn add_resource(&mut self, resource: ResourceRef) {
+        self.resources.insert(resource);
+    }
+
+    /// Records the resources listed in the `yyp`, and the rooms of its room
+    /// order.
+    pub fn add_yyp(&mut self, yyp: &Yyp) {
+        let project = ResourceRef::new(ResourceKind::Project, &yyp.common_data.name);
+
+        for resource in yyp.resources.iter() {
+            if let Some(resource) = ResourceRef::from_path(&resource.id) {
+                self.resources.insert(resource);
+            }
+        }
+        for group in yyp.texture_groups.iter() {
+            self.add_resource(ResourceRef::new(
+                ResourceKind::TextureGroup,
+                &group.common_data.name,
+            ));
+        }
+        for group in yyp.audio_groups.iter() {
+            self.add_resource(ResourceRef::new(
+                ResourceKind::AudioGroup,
+                &group.common_data.name,
+            ));
+        }
+        for (index, node) in yyp.room_order_nodes.iter().enumerate() {
+            self.push(
+                &project,
+                ResourceKind::Room,
+                &node.room_id.name,
+                ReferenceLocation::RoomOrder(index),
+            );
+        }
+
+        self.resources.insert(project);
+    }
+
+    /// Records an Object and its references.
+    pub fn add_object(&mut self, object: &Object) {
+        let from = ResourceRef::new(ResourceKind::Object, &object.common_data.name);
+
+        if let Some(sprite) = &object.sprite_id {
+            self.push(
+                &from,
+                ResourceKind::Sprite,
+                &sprite.name,
+                ReferenceLocation::ObjectSprite,
+            );
+        }
+        if let Some(mask) = &object.sprite_mask_id {
+            self.push(
+                &from,
+                ResourceKind::Sprite,
+                &mask.name,
+                ReferenceLocation::ObjectSpriteMask,
+            );
+        }
+        if let Some(parent) = &object.parent_object_id {
+            self.push(
+                &from,
+                ResourceKind::Object,
+                &parent.name,
+                ReferenceLocation::ObjectParent,
+            );
+        }
+        for (index, event) in object.event_list.iter().enumerate() {
+            if let Some(target) = &event.collision_object_id {
+                self.push(
+                    &from,
+                    ResourceKind::Object,
+                    &target.name,
+                    ReferenceLocation::CollisionEvent(index),
+                );
+            }
+        }
+
+        self.resources.insert(from);
+    }
+
+    /// Records a Sprite and its texture group.
+    pub fn add_sprite(&mut self, sprite: &Sprite) {
+        let from = ResourceRef::new(ResourceKind::Sprite, &sprite.common_data.name);
+
+        self.push(
+            &from,
+            ResourceKind::TextureGroup,
+            &sprite.texture_group_id.name,
+            ReferenceLocation::SpriteTextureGroup,
+        );
+
+        self.resources.insert(from);
+    }
+
+    /// Records a TileSet and its references.
+    pub fn add_tileset(&mut self, tileset: &TileSet) {
+        let from = ResourceRef::new(ResourceKind::TileSet, &tileset.common_data.name);
+
+        if let Some(sprite) = &tileset.sprite_id {
+            self.push(
+                &from,
+                ResourceKind::Sprite,
+                &sprite.name,
+                ReferenceLocation::TileSetSprite,
+            );
+        }
+        self.push(
+            &from,
+            ResourceKind::TextureGroup,
+            &tileset.texture_group_id.name,
+            ReferenceLocation::TileSetTextureGroup,
+        );
+
+        self.resources.insert(from);
+    }
+
+    /// Records a Sound and its audio group.
+    pub fn add_sound(&mut self, sound: &Sound) {
+        let from = ResourceRef::new(ResourceKind::Sound, &sound.common_data.name);
+
+        self.push(
+            &from,
+            ResourceKind::AudioGroup,
+            &sound.audio_group_id.name,
+            ReferenceLocation::SoundAudioGroup,
+        );
+
+        self.resources.insert(from);
+    }
+
+    /// Records a Room and its references, including those made by its
+    /// layers and the instances placed on them.
+    pub fn add_room(&mut self, room: &Room) {
+        fn walk(graph: &mut ReferenceGraph, from: &ResourceRef, layers: &[RoomLayer]) {
+            for layer in layers {
+                let name = &layer.name;
+                match &layer.data {
+                    LayerData::Instance(instances) => {
+                        for instance in instances.instances.iter() {
+                            graph.push(
+                                from,
+                                ResourceKind::Object,
+                                &instance.object_id.name,
+                                ReferenceLocation::RoomInstance {
+                                    layer: name.clone(),
+                                    instance: instance.name.clone(),
+                                },
+                            );
+                        }
+                    }
+                    LayerData::Tilemap(tilemap) => {
+                        if let Some(tileset) = &tilemap.tileset_id {
+                            graph.push(
+                                from,
+                                ResourceKind::TileSet,
+                                &tileset.name,
+                                ReferenceLocation::RoomTilemap {
+                                    layer: name.clone(),
+                                },
+                            );
+                        }
+                    }
+                    LayerData::Asset(assets) => {
+                        for (index, asset) in assets.assets.iter().enumerate() {
+                            let (kind, id) = match asset {
+                                AssetLayerItem::Sprite(sprite) => {
+                                    (ResourceKind::Sprite, &sprite.sprite_id)
+                                }
+                                AssetLayerItem::Sequence(sequence) => {
+                                    (ResourceKind::Sequence, &sequence.sequence_id)
+                                }
+                                AssetLayerItem::Other(_) => continue,
+                            };
+                            if let Some(id) = id {
+                                graph.push(
+                                    from,
+                                    kind,
+                                    &id.name,
+                                    ReferenceLocation::RoomAsset {
+                                        layer: name.clone(),
+                                        index,
+                                    },
+                                );
+                            }
+                        }
+                    }
+                    LayerData::Path(path) => {
+                        if let Some(path) = &path.path_id {
+                            graph.push(
+                                from,
+                                ResourceKind::Path,
+                                &path.name,
+                                ReferenceLocation::RoomPath {
+                                    layer: name.clone(),
+                                },
+                            );
+                        }
+                    }
+                    LayerData::Background(background) => {
+                        if let Some(sprite) = &background.sprite_id {
+                            graph.push(
+                                from,
+                                ResourceKind::Sprite,
+                                &sprite.name,
+                                ReferenceLocation::RoomBackground {
+                                    layer: name.clone(),
+                                },
+                            );
+                        }
+                    }
+                    LayerData::Folder | LayerData::Other(_) => {}
+                }
+
+                walk(graph, from, &layer.layers);
+            }
+        }
+
+        let from = ResourceRef::new(ResourceKind::Room, &room.common_data.name);
+
+        if let Some(parent) = &room.parent_room {
+            self.push(
+                &from,
+                ResourceKind::Room,
+                &parent.name,
+                ReferenceLocation::RoomParent,
+            );
+        }
+        walk(self, &from, &room.layers);
+        for (index, view) in room.views.iter().enumerate() {
+            if let Some(object) = &view.object_id {
+                self.push(
+                    &from,
+                    ResourceKind::Object,
+                    &object.name,
+                    ReferenceLocation::RoomView(index),
+                );
+            }
+        }
+
+        self.resources.insert(from);
+    }
+
+    /// Records a resource from its plain JSON, for resources which have no
+    /// typings or which their typings cannot parse. Every object in `value`
+    /// with a string `name` and a `path` into a resource directory, such as
+    /// `objects/obj_wall/obj_wall.yy`, is a reference to that resource.
+    /// References from the resource to itself, such as a Sprite's frames,
+    /// are skipped.
+    pub fn add_value(&mut self, from: ResourceRef, value: &Value) {
+        fn walk(graph: &mut ReferenceGraph, from: &ResourceRef, value: &Value, pointer: String) {
+            match value {
+                Value::Object(map) => {
+                    if map.get("name").is_some_and(Value::is_string)
+                        && let Some(path) = map.get("path").and_then(Value::as_str)
+                    {
+                        let mut components = path.split('/');
+                        if let Some(kind) = components.next().and_then(ResourceKind::from_directory)
+                            && let Some(name) = components.next()
+                            && (kind, name) != (from.kind, from.name.as_str())
+                        {
+                            graph.push(from, kind, name, ReferenceLocation::Field(pointer));
+                            return;
+                        }
+                    }
+
+                    for (key, value) in map {
+                        let key = key.replace('~', "~0").replace('/', "~1");
+                        walk(graph, from, value, format!("{}/{}", pointer, key));
+                    }
+                }
+                Value::Array(values) => {
+                    for (index, value) in values.iter().enumerate() {
+                        walk(graph, from, value, format!("{}/{}", pointer, index));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        walk(self, &from, value, String::new());
+        self.resources.insert(from);
+    }
+
+    fn push(
+        &mut self,
+        from: &ResourceRef,
+        kind: ResourceKind,
+        name: &str,
+        location: ReferenceLocation,
+    ) {
+        self.references.push(Reference {
+            from: from.clone(),
+            to: ResourceRef::new(kind, name),
+            location,
+        });
+    }
+
+    /// Returns `true` if the resource has been recorded as existing.
+    pub fn contains(&self, resource: &ResourceRef) -> bool {
+        self.resources.contains(resource)
+    }
+
+    /// Returns every resource recorded as existing, sorted by kind and then
+    /// name.
+    pub fn resources(&self) -> impl Iterator<Item = &ResourceRef> {
+        self.resources.iter()
+    }
+
+    /// Returns every reference, in the order they were added.
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+
+    /// Returns the references which `resource` makes to other resources.
+    pub fn uses(&self, resource: &ResourceRef) -> Vec<&Reference> {
+        self.references
+            .iter()
+            .filter(|reference| reference.from == *resource)
+            .collect()
+    }
+
+    /// Returns the references which other resources make to `resource`.
+    pub fn used_by(&self, resource: &ResourceRef) -> Vec<&Reference> {
+        self.references
+            .iter()
+            .filter(|reference| reference.to == *resource)
+            .collect()
+    }
+
+    /// Returns the references to resources which have not been recorded as
+    /// existing. These are usually left behind when a resource is deleted
+    /// or renamed outside of the IDE.
+    pub fn dangling(&self) -> Vec<&Reference> {
+        self.references
+            .iter()
+            .filter(|reference| !self.resources.contains(&reference.to))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AudioGroupId, EventKey, Instance, Instances, ObjectEvent, TexturePath, ViewPath,
+        YypResource,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn layer(name: &str, data: LayerData) -> RoomLayer {
+        RoomLayer {
+            data,
+            visible: true,
+            depth: 0,
+            user_defined_depth: false,
+            grid_x: 32,
+            grid_y: 32,
+            layers: vec![],
+            hierarchy_frozen: false,
+            resource_version: Default::default(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn reference_graph() {
+        let mut graph = ReferenceGraph::new();
+
+        let mut yyp = Yyp::default();
+        yyp.common_data.name = "game".to_string();
+        yyp.resources = [
+            "objects/obj_player",
+            "objects/obj_wall",
+            "sprites/spr_player",
+        ]
+        .iter()
+        .map(|path| {
+            let (base, name) = path.split_once('/').unwrap();
+            YypResource {
+                id: FilesystemPath::new(base, name),
+            }
+        })
+        .collect();
+        yyp.texture_groups = vec![crate::TextureGroup {
+            common_data: crate::CommonData::new("Default".to_string()),
+            ..Default::default()
+        }];
+        yyp.room_order_nodes = vec![crate::RoomOrderId {
+            room_id: FilesystemPath::new("rooms", "rm_level"),
+        }];
+        graph.add_yyp(&yyp);
+
+        graph.add_object(&Object {
+            common_data: crate::CommonData::new("obj_player".to_string()),
+            sprite_id: Some(FilesystemPath::new("sprites", "spr_player")),
+            event_list: vec![
+                ObjectEvent::new(crate::EventType::Create.into()),
+                ObjectEvent::new(EventKey::collision("obj_wall")),
+            ],
+            ..Default::default()
+        });
+        graph.add_sprite(&Sprite {
+            common_data: crate::CommonData::new("spr_player".to_string()),
+            texture_group_id: TexturePath::default(),
+            ..Default::default()
+        });
+        graph.add_sound(&Sound {
+            common_data: crate::CommonData::new("snd_jump".to_string()),
+            audio_group_id: AudioGroupId {
+                name: "audiogroup_default".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        graph.add_tileset(&TileSet {
+            common_data: crate::CommonData::new("ts_walls".to_string()),
+            sprite_id: Some(ViewPath {
+                name: "spr_walls".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        graph.add_room(&Room {
+            common_data: crate::CommonData::new("rm_level".to_string()),
+            layers: vec![layer(
+                "Instances",
+                LayerData::Instance(Instances {
+                    instances: vec![Instance {
+                        name: "inst_player".to_string(),
+                        object_id: FilesystemPath::new("objects", "obj_player"),
+                        ..Default::default()
+                    }],
+                }),
+            )],
+            ..Default::default()
+        });
+
+        let player = ResourceRef::new(ResourceKind::Object, "obj_player");
+        let used_by: Vec<_> = graph
+            .used_by(&player)
+            .into_iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            used_by,
+            vec![
+                "rooms/rm_level (instance `inst_player` on layer `Instances`) -> objects/obj_player"
+            ]
+        );
+
+        let uses: Vec<_> = graph
+            .uses(&player)
+            .into_iter()
+            .map(|reference| (&reference.to, &reference.location))
+            .collect();
+        assert_eq!(
+            uses,
+            vec![
+                (
+                    &ResourceRef::new(ResourceKind::Sprite, "spr_player"),
+                    &ReferenceLocation::ObjectSprite
+                ),
+                (
+                    &ResourceRef::new(ResourceKind::Object, "obj_wall"),
+                    &ReferenceLocation::CollisionEvent(1)
+                ),
+            ]
+        );
+
+        let dangling: Vec<_> = graph
+            .dangling()
+            .into_iter()
+            .map(|reference| reference.to.to_string())
+            .collect();
+        assert_eq!(
+            dangling,
+            vec!["audiogroups/audiogroup_default", "sprites/spr_walls"]
+        );
+
+        assert_eq!(
+            ResourceRef::from_path(&FilesystemPath::new("tilesets", "ts_walls")),
+            Some(ResourceRef::new(ResourceKind::TileSet, "ts_walls"))
+        );
+    }
+
+    #[test]
+    fn room_layers() {
+        let text = include_str!("../data/rooms/rm_overworld.yy");
+        let room: Room = serde_json::from_str(
+            &crate::TrailingCommaUtility::clear_trailing_comma_once(text),
+        )
+        .unwrap();
+
+        let mut graph = ReferenceGraph::new();
+        graph.add_room(&room);
+
+        let rm_overworld = ResourceRef::new(ResourceKind::Room, "rm_overworld");
+        let uses: Vec<_> = graph
+            .uses(&rm_overworld)
+            .into_iter()
+            .map(|reference| reference.to.to_string())
+            .collect();
+        assert_eq!(
+            uses,
+            vec![
+                "objects/obj_player",
+                "paths/pth_patrol",
+                "sprites/spr_tree",
+                "sequences/seq_waterfall",
+                "tilesets/ts_grass",
+                "objects/obj_player",
+            ]
+        );
+
+        // the plain JSON finds the same references.
+        let mut from_json = ReferenceGraph::new();
+        from_json.add_value(rm_overworld.clone(), &serde_json::to_value(&room).unwrap());
+        let mut typed: Vec<_> = graph.references().iter().map(|r| &r.to).collect();
+        let mut untyped: Vec<_> = from_json.references().iter().map(|r| &r.to).collect();
+        typed.sort();
+        untyped.sort();
+        assert_eq!(typed, untyped);
+        assert!(
+            from_json
+                .uses(&rm_overworld)
+                .iter()
+                .any(|r| r.location == ReferenceLocation::Field("/layers/2/pathId".to_string()))
+        );
+    }
+}
@@ -1,5 +1,9 @@
-use crate::{ObjectOverrideProperty, ResourceVersion, Tags, ViewPath};
-use serde::{Deserialize, Serialize};
+mod instance_properties;
+pub use instance_properties::*;
+
+use crate::{FilesystemPath, ObjectOverrideProperty, ResourceVersion};
+use serde::{Deserialize, Deserializer, Serialize, de::Error};
+use serde_json::{Map, Value};
 use smart_default::SmartDefault;
 
 #[derive(Debug, Serialize, Deserialize, Default, PartialEq, Clone)]
@@ -20,7 +24,7 @@ pub struct Room {
     /// This is only meaningful if `parent_room` is `Some`.
     pub inherit_layers: bool,
 
-    pub instance_creation_order: Vec<ViewPath>,
+    pub instance_creation_order: Vec<FilesystemPath>,
 
     /// Is this used in DragNDrop? Hopefully not! that would get messy.
     pub is_dnd: bool,
@@ -31,7 +35,7 @@ pub struct Room {
     pub parent: crate::ViewPath,
 
     /// The path of the parent room.
-    pub parent_room: Option<ViewPath>,
+    pub parent_room: Option<FilesystemPath>,
 
     pub physics_settings: PhysicsSettings,
 
@@ -39,7 +43,10 @@ pub struct Room {
 
     pub sequence_id: Option<()>,
 
-    pub tags: Tags,
+    /// The tags associated with this room.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub tags: Vec<String>,
 
     /// Eight (at least) views. Most users won't have anything
     /// meaningful here.
@@ -70,7 +77,7 @@ pub struct RoomView {
     pub vspeed: i32,
 
     #[serde(rename = "objectId")]
-    pub object_id: Option<ViewPath>,
+    pub object_id: Option<FilesystemPath>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -106,19 +113,37 @@ pub enum LayerData {
     Asset(Assets),
     #[serde(rename = "GMRBackgroundLayer")]
     Background(BackgroundSprite),
+    #[serde(rename = "GMRPathLayer")]
+    Path(PathLayer),
     #[serde(rename = "GMRLayer")]
     Folder,
+    /// Any other kind of layer, such as an effect layer, kept as it was
+    /// written so that it survives being saved again.
+    #[serde(untagged)]
+    Other(OtherLayer),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 pub struct Assets {
-    pub assets: Vec<Asset>,
+    pub assets: Vec<AssetLayerItem>,
+}
+
+/// One item on an asset layer.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum AssetLayerItem {
+    Sprite(Asset),
+    Sequence(SequenceAsset),
+    /// Any other kind of item, such as a particle system or text, kept as it
+    /// was written.
+    #[serde(deserialize_with = "untyped_asset")]
+    Other(Value),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Asset {
-    pub sprite_id: Option<ViewPath>,
+    pub sprite_id: Option<FilesystemPath>,
     pub head_position: f64,
     pub rotation: f64,
     pub scale_x: f64,
@@ -126,7 +151,7 @@ pub struct Asset {
     pub animation_speed: f64,
     #[serde(rename = "colour")]
     pub color: usize,
-    pub inherited_item_id: Option<ViewPath>,
+    pub inherited_item_id: Option<FilesystemPath>,
     pub frozen: bool,
     pub ignore: bool,
     pub inherit_item_settings: bool,
@@ -137,10 +162,32 @@ pub struct Asset {
     pub resource_type: ConstGMSpriteGraphic,
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SequenceAsset {
+    pub sequence_id: Option<FilesystemPath>,
+    pub head_position: f64,
+    pub rotation: f64,
+    pub scale_x: f64,
+    pub scale_y: f64,
+    pub animation_speed: f64,
+    #[serde(rename = "colour")]
+    pub color: usize,
+    pub inherited_item_id: Option<FilesystemPath>,
+    pub frozen: bool,
+    pub ignore: bool,
+    pub inherit_item_settings: bool,
+    pub x: f64,
+    pub y: f64,
+    pub resource_version: ResourceVersion,
+    pub name: String,
+    pub resource_type: ConstGMSequenceGraphic,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct BackgroundSprite {
-    pub sprite_id: Option<ViewPath>,
+    pub sprite_id: Option<FilesystemPath>,
     #[serde(rename = "colour")]
     pub color: usize,
     pub x: i32,
@@ -160,12 +207,67 @@ pub struct BackgroundSprite {
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Tilemap {
-    pub tileset_id: Option<ViewPath>,
+    pub tileset_id: Option<FilesystemPath>,
     pub x: i32,
     pub y: i32,
     pub tiles: TilemapTileData,
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PathLayer {
+    pub path_id: Option<FilesystemPath>,
+    #[serde(rename = "colour")]
+    pub color: usize,
+}
+
+/// A layer of a kind which is not typed here. `resource_type` is the layer's
+/// `resourceType`, and `fields` holds every field which is not part of
+/// [`RoomLayer`] itself.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct OtherLayer {
+    #[serde(deserialize_with = "untyped_layer_type")]
+    pub resource_type: String,
+    #[serde(flatten)]
+    pub fields: Map<String, Value>,
+}
+
+/// Only layers which are not typed can be an [`OtherLayer`], so a typed
+/// layer which does not parse is an error rather than being kept as JSON.
+fn untyped_layer_type<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    const TYPED: [&str; 6] = [
+        "GMRInstanceLayer",
+        "GMRTileLayer",
+        "GMRAssetLayer",
+        "GMRBackgroundLayer",
+        "GMRPathLayer",
+        "GMRLayer",
+    ];
+
+    let resource_type = String::deserialize(deserializer)?;
+    if TYPED.contains(&resource_type.as_str()) {
+        return Err(D::Error::custom(format!(
+            "invalid `{}` layer",
+            resource_type
+        )));
+    }
+
+    Ok(resource_type)
+}
+
+/// As [`untyped_layer_type`], for the items of an asset layer.
+fn untyped_asset<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Value, D::Error> {
+    let value = Value::deserialize(deserializer)?;
+    if let Some(resource_type @ ("GMRSpriteGraphic" | "GMRSequenceGraphicElement")) =
+        value["resourceType"].as_str()
+    {
+        return Err(D::Error::custom(format!("invalid `{}`", resource_type)));
+    }
+
+    Ok(value)
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
 pub struct TilemapTileData {
     #[serde(rename = "TileDataFormat")]
@@ -193,7 +295,7 @@ pub struct Instance {
     pub name: String,
     pub properties: Vec<ObjectOverrideProperty>,
     pub is_dnd: bool,
-    pub object_id: ViewPath,
+    pub object_id: FilesystemPath,
     pub inherit_code: bool,
     pub has_creation_code: bool,
     #[serde(rename = "colour")]
@@ -203,7 +305,7 @@ pub struct Instance {
     pub scale_y: f64,
     pub image_index: i32,
     pub image_speed: f64,
-    pub inherited_item_id: Option<ViewPath>,
+    pub inherited_item_id: Option<FilesystemPath>,
     pub frozen: bool,
     pub ignore: bool,
     pub inherit_item_settings: bool,
@@ -232,6 +334,13 @@ pub enum ConstGMSpriteGraphic {
     Const,
 }
 
+#[derive(Debug, Copy, Serialize, Deserialize, SmartDefault, PartialEq, Eq, Clone)]
+pub enum ConstGMSequenceGraphic {
+    #[serde(rename = "GMRSequenceGraphicElement")]
+    #[default]
+    Const,
+}
+
 #[derive(Debug, Copy, Serialize, Deserialize, SmartDefault, PartialEq, Eq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RoomSettings {
@@ -265,3 +374,80 @@ pub struct PhysicsSettings {
     #[serde(rename = "PhysicsWorldPixToMetres")]
     physics_world_pix_to_meters: f64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::TrailingCommaUtility;
+    use include_dir::{Dir, DirEntry, include_dir};
+
+    static ALL_ROOMS: Dir<'static> = include_dir!("$CARGO_MANIFEST_DIR/data/rooms");
+
+    #[test]
+    fn trivial_room_parsing() {
+        let tcu = TrailingCommaUtility::new();
+
+        for room_file in ALL_ROOMS.find("**/*.yy").unwrap() {
+            if let DirEntry::File(file) = room_file {
+                println!("parsing {}", file.path().display());
+                let our_str = std::str::from_utf8(file.contents()).unwrap();
+                let our_str = tcu.clear_trailing_comma(our_str);
+                let room: Room = serde_json::from_str(&our_str).unwrap();
+
+                let instances = room.instances();
+                assert!(instances.iter().all(|(_, i)| !i.object_id.name.is_empty()));
+
+                let again: Room =
+                    serde_json::from_str(&serde_json::to_string(&room).unwrap()).unwrap();
+                assert_eq!(room, again);
+            }
+        }
+    }
+
+    #[test]
+    fn untyped_layers() {
+        let tcu = TrailingCommaUtility::new();
+        let file = ALL_ROOMS.get_file("rm_overworld.yy").unwrap();
+        let room_str = tcu.clear_trailing_comma(file.contents_utf8().unwrap());
+        let room: Room = serde_json::from_str(&room_str).unwrap();
+
+        let LayerData::Other(effect) = &room.layers[0].data else {
+            panic!("expected an untyped effect layer");
+        };
+        assert_eq!(effect.resource_type, "GMREffectLayer");
+        assert_eq!(effect.fields["effectType"], "_filter_vignette");
+        assert!(!effect.fields.contains_key("depth"));
+
+        let LayerData::Path(path) = &room.layers[2].data else {
+            panic!("expected a path layer");
+        };
+        assert_eq!(path.path_id.as_ref().unwrap().name, "pth_patrol");
+
+        let LayerData::Asset(assets) = &room.layers[3].data else {
+            panic!("expected an asset layer");
+        };
+        assert!(matches!(assets.assets[0], AssetLayerItem::Sprite(_)));
+        assert!(matches!(assets.assets[1], AssetLayerItem::Sequence(_)));
+        assert!(matches!(assets.assets[2], AssetLayerItem::Other(_)));
+
+        let value = serde_json::to_value(&room).unwrap();
+        assert_eq!(value["layers"][0]["resourceType"], "GMREffectLayer");
+        assert_eq!(
+            value["layers"][0]["properties"][0]["name"],
+            "g_VignetteEdges"
+        );
+        assert_eq!(value["layers"][0]["depth"], 0);
+
+        // a typed layer which does not parse is not kept as JSON.
+        let mut broken = value.clone();
+        broken["layers"][2]["pathId"] = Value::from(7);
+        assert!(serde_json::from_value::<Room>(broken).is_err());
+        let mut broken = value.clone();
+        broken["layers"][3]["assets"][0]["x"] = Value::from("left");
+        assert!(serde_json::from_value::<Room>(broken).is_err());
+        assert_eq!(
+            value["layers"][3]["assets"][2]["particleSystemId"]["name"],
+            "ps_smoke"
+        );
+    }
+}
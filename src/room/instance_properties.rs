@@ -0,0 +1,255 @@
+use super::{Instance, LayerData, Room, RoomLayer};
+use crate::{
+    ObjectLookup, ObjectOverrideProperty, ObjectProperty, PropertyValue, PropertyValueError,
+    ResolveError,
+};
+
+/// The final property values of an instance placed in a Room. See
+/// [`Room::instance_property_values`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct InstanceProperties<'a> {
+    /// The instance.
+    pub instance: &'a Instance,
+
+    /// The name of the layer which the instance is placed on.
+    pub layer: &'a str,
+
+    /// Every property of the instance's object, including inherited ones,
+    /// in the order the IDE lists them.
+    pub values: Vec<InstancePropertyValue<'a>>,
+
+    /// Overrides on the instance which name a property that neither its
+    /// object nor any of the object's parents define. These are usually left
+    /// behind when a property is deleted, and are ignored by the runtime.
+    pub unknown_overrides: Vec<&'a ObjectOverrideProperty>,
+}
+
+/// The final value of one property of a placed instance.
+#[derive(Debug, PartialEq, Clone)]
+pub struct InstancePropertyValue<'a> {
+    /// The definition of the property.
+    pub definition: &'a ObjectProperty,
+
+    /// The serialized value, after applying the object chain and then the
+    /// instance's own overrides.
+    pub value: &'a str,
+
+    /// Whether the value comes from an override on the instance itself,
+    /// rather than from the object chain.
+    pub overridden_by_instance: bool,
+}
+
+impl InstancePropertyValue<'_> {
+    /// Parses `value` according to the property's `var_type`.
+    ///
+    /// # Errors
+    ///
+    /// Errors if `value` is not a valid value of the property's type.
+    pub fn typed_value(&self) -> Result<PropertyValue, PropertyValueError> {
+        PropertyValue::parse(self.definition.var_type, self.value)
+    }
+}
+
+impl Room {
+    /// Returns every instance placed in the Room, along with the name of its
+    /// layer. Instances are listed layer by layer, in the order of `layers`,
+    /// with each layer's sublayers listed before the next layer.
+    pub fn instances(&self) -> Vec<(&str, &Instance)> {
+        fn walk<'a>(layers: &'a [RoomLayer], output: &mut Vec<(&'a str, &'a Instance)>) {
+            for layer in layers {
+                if let LayerData::Instance(instances) = &layer.data {
+                    for instance in instances.instances.iter() {
+                        output.push((&layer.name, instance));
+                    }
+                }
+                walk(&layer.layers, output);
+            }
+        }
+
+        let mut output = vec![];
+        walk(&self.layers, &mut output);
+
+        output
+    }
+
+    /// Works out the final value of every property of every instance placed
+    /// in the Room. Each instance starts with the properties of its object,
+    /// resolved through the object's parents as in
+    /// [`Object::resolve`](crate::Object::resolve), and then has its own
+    /// overrides applied.
+    ///
+    /// # Errors
+    ///
+    /// Errors if an instance's object, or one of its parents, cannot be found
+    /// in `lookup`, or if an object inherits from itself.
+    pub fn instance_property_values<'a, L: ObjectLookup + ?Sized>(
+        &'a self,
+        lookup: &'a L,
+    ) -> Result<Vec<InstanceProperties<'a>>, ResolveError> {
+        self.instances()
+            .into_iter()
+            .map(|(layer, instance)| {
+                let object = lookup
+                    .get_object(&instance.object_id.name)
+                    .ok_or_else(|| ResolveError::MissingObject(instance.object_id.name.clone()))?;
+                let resolved = object.resolve(lookup)?;
+
+                let mut values: Vec<_> = resolved
+                    .properties
+                    .iter()
+                    .map(|property| InstancePropertyValue {
+                        definition: property.definition,
+                        value: property.value,
+                        overridden_by_instance: false,
+                    })
+                    .collect();
+
+                let mut unknown_overrides = vec![];
+                for override_property in instance.properties.iter() {
+                    let name = override_property.property_id.as_ref().map(|id| &id.name);
+                    match values
+                        .iter_mut()
+                        .find(|value| Some(&value.definition.common_data.name) == name)
+                    {
+                        Some(value) => {
+                            value.value = &override_property.value;
+                            value.overridden_by_instance = true;
+                        }
+                        None => unknown_overrides.push(override_property),
+                    }
+                }
+
+                Ok(InstanceProperties {
+                    instance,
+                    layer,
+                    values,
+                    unknown_overrides,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FilesystemPath, Instances, Object, ObjectPropertyTypes};
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    fn property(name: &str, var_type: ObjectPropertyTypes, value: &str) -> ObjectProperty {
+        ObjectProperty {
+            common_data: crate::CommonData::new(name.to_string()),
+            var_type,
+            value: value.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn override_property(owner: &str, name: &str, value: &str) -> ObjectOverrideProperty {
+        ObjectOverrideProperty {
+            property_id: Some(FilesystemPath {
+                name: name.to_string(),
+                path: FilesystemPath::new_path("objects", owner),
+            }),
+            object_id: FilesystemPath::new("objects", owner),
+            value: value.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn layer(name: &str, data: LayerData, layers: Vec<RoomLayer>) -> RoomLayer {
+        RoomLayer {
+            data,
+            visible: true,
+            depth: 0,
+            user_defined_depth: false,
+            grid_x: 32,
+            grid_y: 32,
+            layers,
+            hierarchy_frozen: false,
+            resource_version: Default::default(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn instance_property_values() {
+        let mut parent = Object {
+            common_data: crate::CommonData::new("par_door".to_string()),
+            ..Default::default()
+        };
+        parent.properties = vec![
+            property("locked", ObjectPropertyTypes::Boolean, "False"),
+            property("key_id", ObjectPropertyTypes::Integer, "0"),
+        ];
+        let door = Object {
+            common_data: crate::CommonData::new("obj_door".to_string()),
+            parent_object_id: Some(FilesystemPath::new("objects", "par_door")),
+            overridden_properties: vec![override_property("par_door", "key_id", "3")],
+            ..Default::default()
+        };
+        let objects: HashMap<_, _> = [parent, door]
+            .into_iter()
+            .map(|object| (object.common_data.name.clone(), object))
+            .collect();
+
+        let instance = Instance {
+            name: "inst_door".to_string(),
+            object_id: FilesystemPath::new("objects", "obj_door"),
+            properties: vec![
+                override_property("par_door", "locked", "True"),
+                override_property("par_door", "colour", "$FF0000FF"),
+            ],
+            ..Default::default()
+        };
+        let room = Room {
+            layers: vec![layer(
+                "Gameplay",
+                LayerData::Folder,
+                vec![layer(
+                    "Instances",
+                    LayerData::Instance(Instances {
+                        instances: vec![instance],
+                    }),
+                    vec![],
+                )],
+            )],
+            ..Default::default()
+        };
+
+        let output = room.instance_property_values(&objects).unwrap();
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].layer, "Instances");
+
+        let values: Vec<_> = output[0]
+            .values
+            .iter()
+            .map(|v| {
+                (
+                    v.definition.common_data.name.as_str(),
+                    v.value,
+                    v.overridden_by_instance,
+                )
+            })
+            .collect();
+        assert_eq!(
+            values,
+            vec![("locked", "True", true), ("key_id", "3", false)]
+        );
+        assert_eq!(
+            output[0].values[0].typed_value().unwrap(),
+            PropertyValue::Boolean(true)
+        );
+        assert_eq!(
+            output[0].unknown_overrides,
+            vec![&room.instances()[0].1.properties[1]]
+        );
+
+        let empty: HashMap<String, Object> = HashMap::new();
+        assert_eq!(
+            room.instance_property_values(&empty).unwrap_err(),
+            ResolveError::MissingObject("obj_door".to_string())
+        );
+    }
+}
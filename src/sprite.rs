@@ -6,6 +6,37 @@ pub use sequence::*;
 mod frames_layers;
 pub use frames_layers::*;
 
+mod images;
+pub use images::*;
+
+mod origin;
+pub use origin::*;
+
+mod canvas;
+pub use canvas::*;
+
+mod nine_slice;
+pub use nine_slice::*;
+
+mod composite;
+mod animation;
+mod broadcast;
+
+mod spine;
+pub use spine::*;
+
+mod duplicates;
+pub use duplicates::*;
+
+mod sheet;
+pub use sheet::*;
+
+mod aseprite;
+pub use aseprite::*;
+
+mod texture_packer;
+pub use texture_packer::*;
+
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use smart_default::SmartDefault;
@@ -71,17 +102,18 @@ pub struct Sprite {
     pub swatch_colours: serde_json::Value,
 
     /// The precision for Vector sprites. Its default is `2.525`, a number
-    /// which is very odd in my opinion.
+    /// which is very odd in my opinion. It is written for every type of
+    /// sprite, but only used by [`SpriteType::Swf`] sprites.
     #[default(2.525)]
     pub swf_precision: f64,
 
     /// This is the Path to the Texture Group Id.
     pub texture_group_id: TexturePath,
 
-    /// The type of sprite, whether a bitmap or a vector sprite.
-    /// Right now, we don't handle this intelligently.
+    /// The type of sprite, whether a bitmap, a vector sprite, or a Spine
+    /// skeleton. Only bitmap sprites have frame images.
     #[serde(rename = "type")]
-    pub resource_sprite_type: usize,
+    pub resource_sprite_type: SpriteType,
 
     #[serde(rename = "VTile")]
     pub v_tile: bool,
@@ -90,6 +122,110 @@ pub struct Sprite {
     pub width: NonZeroUsize,
 }
 
+impl Sprite {
+    /// Creates a new Sprite with a single, visible layer and an empty frames
+    /// track. Frames are added with [`push_frame`](Self::push_frame).
+    ///
+    /// The `parent` and `texture_group_id` are left at their defaults, so users
+    /// will generally want to set those before serializing.
+    pub fn new(name: &str, width: NonZeroUsize, height: NonZeroUsize) -> Self {
+        Self {
+            common_data: crate::CommonData::new(name.to_string()),
+            bbox_mode: BBoxMode::FullImage,
+            bbox_right: width.get() as i32 - 1,
+            bbox_bottom: height.get() as i32 - 1,
+            width,
+            height,
+            layers: vec![SpriteLayer {
+                common_data: crate::CommonData::new(LayerId::new()),
+                visible: true,
+                ..Default::default()
+            }],
+            sequence: SpriteSequence {
+                backdrop_width: 1920,
+                backdrop_height: 1080,
+                playback_speed: 30.0,
+                tracks: vec![Track::default()],
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Appends a new frame to the end of the Sprite's animation, lasting for
+    /// `length` units of the sequence, and returns its Id. The sequence's
+    /// `length` is extended to match.
+    ///
+    /// If the Sprite has no frames track, one is created.
+    pub fn push_frame(&mut self, length: f64) -> FrameId {
+        let frame_id = FrameId::new();
+        self.frames.push(crate::CommonData::new(frame_id));
+
+        let channel_id = FilesystemPath::new("sprites", &self.common_data.name);
+        let key = self.sequence.length;
+
+        if self.sequence.tracks.is_empty() {
+            self.sequence.tracks.push(Track::default());
+        }
+        self.sequence.tracks[0]
+            .keyframes
+            .keyframes
+            .push(SpriteKeyframe {
+                channels: Channels {
+                    zero: SpriteZeroChannel {
+                        id: FilesystemPath {
+                            name: frame_id.inner().to_string(),
+                            path: channel_id.path,
+                        },
+                        ..Default::default()
+                    },
+                },
+                key,
+                length,
+                ..Default::default()
+            });
+        self.sequence.length += length;
+
+        frame_id
+    }
+
+    /// Returns the frames track of the Sprite's sequence, if it has one.
+    /// Sprites only ever have the one track.
+    pub fn frames_track(&self) -> Option<&Track> {
+        self.sequence.tracks.first()
+    }
+
+    /// Returns the frames track of the Sprite's sequence mutably, if it has one.
+    pub fn frames_track_mut(&mut self) -> Option<&mut Track> {
+        self.sequence.tracks.first_mut()
+    }
+}
+
+/// The kind of image data a Sprite holds.
+#[derive(
+    Serialize_repr,
+    Deserialize_repr,
+    PartialEq,
+    Debug,
+    SmartDefault,
+    Copy,
+    Clone,
+    Eq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[repr(u8)]
+pub enum SpriteType {
+    /// A regular Sprite, with a PNG for each frame.
+    #[default]
+    Bitmap,
+    /// A vector Sprite, imported from an SWF file.
+    Swf,
+    /// A Spine skeleton, with a JSON file and a texture atlas.
+    Spine,
+}
+
 #[derive(
     Serialize_repr,
     Deserialize_repr,
@@ -178,12 +314,9 @@ pub struct NineSlice {
     /// to toggle nine-slicing on and off.
     pub enabled: bool,
 
-    /// The guide color for each bound, listed in this order:
-    /// 0: Left
-    /// 1: Top
-    /// 2: Right
-    /// 3: Bottom
-    pub guide_colour: [GmEncodedColor; 4],
+    /// The guide color for each bound. This is serialized as an array, in
+    /// the order left, top, right, bottom.
+    pub guide_colour: NineSliceGuideColours,
 
     /// The highlight color to use for the highlighted segment. Why in the world is this
     /// user configurable? But it is!
@@ -198,29 +331,24 @@ pub struct NineSlice {
     /// The right bound.
     pub right: u64,
 
-    /// This is an array of 5 tile mode sprites. I am leaving this typed as
-    /// a raw array right now, rather than writing a custom serde impl, due to time
-    /// constraints. In the future, I will make this into a nice struct.
-    /// The following are each index:
-    /// 0: Left
-    /// 1: Top,
-    /// 2: Right
-    /// 3: Bottom
-    /// 4: Center
-    pub tile_mode: [TileMode; 5],
+    /// The tile mode of each edge and the center. This is serialized as an
+    /// array, in the order left, top, right, bottom, center.
+    pub tile_mode: NineSliceTileModes,
 
     /// The top bound.
     pub top: u64,
 }
 
-/// A color encoded in a u64 in BGR format. It does not contain Alpha.
-#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+/// A color encoded in a u64 in BGR format, so that red is the lowest byte.
+/// The IDE stores an alpha in the byte above blue for some colours, such as
+/// the nine-slice guides.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone, Copy, Hash)]
 #[repr(transparent)]
 #[serde(transparent)]
 pub struct GmEncodedColor(u64);
 
 /// The highlight style for nineslice support.
-#[derive(Debug, Serialize_repr, Deserialize_repr, SmartDefault, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize_repr, Deserialize_repr, SmartDefault, PartialEq, Eq, Clone, Copy)]
 #[repr(u8)]
 pub enum HighlightStyle {
     /// This inverts the colors below it.
@@ -231,7 +359,7 @@ pub enum HighlightStyle {
 }
 
 /// The tilemodes for each nine-slice. Refer to GM's documentation on what each does.
-#[derive(Debug, Serialize_repr, Deserialize_repr, SmartDefault, PartialEq, Eq, Clone)]
+#[derive(Debug, Serialize_repr, Deserialize_repr, SmartDefault, PartialEq, Eq, Clone, Copy)]
 #[repr(u8)]
 pub enum TileMode {
     #[default]
@@ -250,6 +378,10 @@ gm_const!(
     Sequence -> "GMSequence",
     SpriteEvent -> "KeyframeStore<MessageEventKeyframe>",
     SpriteMoment -> "KeyframeStore<MomentsEventKeyframe>",
+    SpriteMessageKeyframe -> "Keyframe<MessageEventKeyframe>",
+    MessageEventKeyframe -> "MessageEventKeyframe",
+    SpriteMomentKeyframe -> "Keyframe<MomentsEventKeyframe>",
+    MomentsEventKeyframe -> "MomentsEventKeyframe",
     SpriteTrackName -> "frames",
     SpriteFramesTrack -> "GMSpriteFramesTrack",
     SpriteKeyframes -> "KeyframeStore<SpriteFrameKeyframe>",
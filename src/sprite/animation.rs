@@ -0,0 +1,229 @@
+use super::{FrameId, SequencePlayback, Sprite, SpriteImageError};
+use image::RgbaImage;
+use std::{collections::HashMap, io::Write, path::Path};
+
+impl Sprite {
+    /// The frames of the Sprite's animation in the order they play, each with
+    /// how many seconds it is shown for in a game running at `game_fps`.
+    ///
+    /// Disabled keyframes are skipped. Ping-pong sequences are unrolled into
+    /// a single forwards and backwards pass. If the sequence does not play,
+    /// only its first frame is returned, lasting for no time at all.
+    pub fn animation_frames(&self, game_fps: f64) -> Vec<(FrameId, f64)> {
+        let Some(track) = self.frames_track() else {
+            return vec![];
+        };
+
+        let mut keyframes: Vec<_> = track
+            .keyframes
+            .keyframes
+            .iter()
+            .filter(|keyframe| !keyframe.disabled)
+            .filter_map(|keyframe| Some((keyframe.key, keyframe.length, keyframe.frame_id()?)))
+            .collect();
+        keyframes.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let units_per_second = self.sequence.units_per_second(game_fps);
+        if !(units_per_second > 0.0 && units_per_second.is_finite()) {
+            keyframes.truncate(1);
+            return keyframes
+                .into_iter()
+                .map(|(_, _, frame)| (frame, 0.0))
+                .collect();
+        }
+
+        let mut output: Vec<_> = keyframes
+            .into_iter()
+            .map(|(_, length, frame)| (frame, length / units_per_second))
+            .collect();
+
+        if self.sequence.playback == SequencePlayback::PingPong && output.len() > 2 {
+            let back: Vec<_> = output[1..output.len() - 1].iter().rev().copied().collect();
+            output.extend(back);
+        }
+
+        output
+    }
+
+    /// Writes the Sprite's animation as an animated GIF, using the frame
+    /// images in the Sprite's directory; see
+    /// [`composite_frame`](Self::composite_frame). The GIF loops unless the
+    /// sequence only plays once.
+    ///
+    /// GIFs only support delays in hundredths of a second, and a single level
+    /// of transparency, so partially transparent pixels are made fully
+    /// opaque or fully transparent.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the Sprite is not a bitmap Sprite, has no frames, or is too
+    /// large for a GIF, or if an image cannot be read or the GIF cannot be
+    /// written.
+    pub fn export_gif<W: Write>(
+        &self,
+        sprite_dir: &Path,
+        game_fps: f64,
+        output: W,
+    ) -> Result<(), SpriteImageError> {
+        let width = u16::try_from(self.width.get()).map_err(|_| SpriteImageError::TooLarge)?;
+        let height = u16::try_from(self.height.get()).map_err(|_| SpriteImageError::TooLarge)?;
+        let frames = self.animation_images(sprite_dir, game_fps)?;
+
+        let mut encoder = gif::Encoder::new(output, width, height, &[])?;
+        if self.sequence.playback != SequencePlayback::Once {
+            encoder.set_repeat(gif::Repeat::Infinite)?;
+        }
+
+        for (image, seconds) in frames {
+            let mut pixels = image.as_raw().clone();
+            let mut frame = gif::Frame::from_rgba_speed(width, height, &mut pixels, 10);
+            frame.delay = (seconds * 100.0).round().clamp(0.0, u16::MAX.into()) as u16;
+            frame.dispose = gif::DisposalMethod::Background;
+
+            encoder.write_frame(&frame)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the Sprite's animation as an animated PNG, using the frame
+    /// images in the Sprite's directory; see
+    /// [`composite_frame`](Self::composite_frame). The APNG loops unless the
+    /// sequence only plays once.
+    ///
+    /// Delays are written in milliseconds, so frames longer than about a
+    /// minute are cut short.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the Sprite is not a bitmap Sprite or has no frames, or if an
+    /// image cannot be read or the APNG cannot be written.
+    pub fn export_apng<W: Write>(
+        &self,
+        sprite_dir: &Path,
+        game_fps: f64,
+        output: W,
+    ) -> Result<(), SpriteImageError> {
+        let frames = self.animation_images(sprite_dir, game_fps)?;
+        let plays = if self.sequence.playback == SequencePlayback::Once {
+            1
+        } else {
+            0
+        };
+
+        let mut encoder =
+            png::Encoder::new(output, self.width.get() as u32, self.height.get() as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_animated(frames.len() as u32, plays)?;
+
+        let mut writer = encoder.write_header()?;
+        for (image, seconds) in frames {
+            let delay = (seconds * 1000.0).round().clamp(0.0, u16::MAX.into()) as u16;
+            writer.set_frame_delay(delay, 1000)?;
+            writer.write_image_data(image.as_raw())?;
+        }
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Loads the image of each frame of the animation, reading every frame
+    /// only once.
+    fn animation_images(
+        &self,
+        sprite_dir: &Path,
+        game_fps: f64,
+    ) -> Result<Vec<(RgbaImage, f64)>, SpriteImageError> {
+        let frames = self.animation_frames(game_fps);
+        if frames.is_empty() {
+            return Err(SpriteImageError::NoFrames);
+        }
+
+        let mut images: HashMap<FrameId, RgbaImage> = HashMap::new();
+        let mut output = Vec::with_capacity(frames.len());
+        for (frame, seconds) in frames {
+            let image = match images.get(&frame) {
+                Some(image) => image.clone(),
+                None => {
+                    let image = self.composite_frame(sprite_dir, frame)?;
+                    images.insert(frame, image.clone());
+                    image
+                }
+            };
+            output.push((image, seconds));
+        }
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::num::NonZeroUsize;
+
+    fn sprite(dir: &Path) -> Sprite {
+        let two = NonZeroUsize::new(2).unwrap();
+        let mut sprite = Sprite::new("spr_test", two, two);
+        sprite.sequence.playback_speed = 10.0;
+        sprite.sequence.playback = SequencePlayback::PingPong;
+
+        for (length, colour) in [
+            (1.0, [255, 0, 0, 255]),
+            (2.0, [0, 255, 0, 255]),
+            (1.0, [0; 4]),
+        ] {
+            let frame = sprite.push_frame(length);
+            let image = RgbaImage::from_pixel(2, 2, image::Rgba(colour));
+            sprite.write_frame_image(dir, frame, &image).unwrap();
+        }
+
+        sprite
+    }
+
+    #[test]
+    fn export_gif() {
+        let dir = tempfile::tempdir().unwrap();
+        let sprite = sprite(dir.path());
+
+        let frames = sprite.animation_frames(60.0);
+        assert_eq!(
+            frames
+                .iter()
+                .map(|(_, seconds)| *seconds)
+                .collect::<Vec<_>>(),
+            vec![0.1, 0.2, 0.1, 0.2]
+        );
+        assert_eq!(frames[3].0, frames[1].0);
+
+        let mut gif = vec![];
+        sprite.export_gif(dir.path(), 60.0, &mut gif).unwrap();
+
+        let mut decoder = gif::DecodeOptions::new();
+        decoder.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = decoder.read_info(gif.as_slice()).unwrap();
+        let mut delays = vec![];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            delays.push(frame.delay);
+        }
+        assert_eq!(delays, vec![10, 20, 10, 20]);
+    }
+
+    #[test]
+    fn export_apng() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut sprite = sprite(dir.path());
+        sprite.sequence.playback = SequencePlayback::Once;
+
+        let mut apng = vec![];
+        sprite.export_apng(dir.path(), 60.0, &mut apng).unwrap();
+
+        let decoder = png::Decoder::new(apng.as_slice());
+        let reader = decoder.read_info().unwrap();
+        let animation = reader.info().animation_control.unwrap();
+        assert_eq!(animation.num_frames, 3);
+        assert_eq!(animation.num_plays, 1);
+    }
+}
@@ -0,0 +1,238 @@
+use super::{ImportedSprite, SheetFrames, SheetSize, SpriteImportError};
+use crate::ResourceNameValidator;
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+
+/// The JSON sidecar which Aseprite writes next to an exported spritesheet,
+/// with either the "hash" or "array" frame layout.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Clone)]
+pub struct AsepriteSheet {
+    /// Every frame of the sheet, in the order of Aseprite's timeline.
+    pub frames: SheetFrames,
+
+    /// Metadata about the sheet, including its tags.
+    pub meta: AsepriteMeta,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AsepriteMeta {
+    /// The filename of the sheet's image, relative to the JSON file.
+    pub image: Option<String>,
+
+    /// The size of the sheet's image.
+    pub size: Option<SheetSize>,
+
+    /// The tags of the timeline. Each tag becomes its own Sprite.
+    #[serde(default)]
+    pub frame_tags: Vec<AsepriteTag>,
+}
+
+/// A named range of frames within the timeline.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct AsepriteTag {
+    pub name: String,
+
+    /// The first frame of the tag, inclusive.
+    pub from: usize,
+
+    /// The last frame of the tag, inclusive.
+    pub to: usize,
+
+    #[serde(default)]
+    pub direction: AsepriteDirection,
+}
+
+impl AsepriteTag {
+    /// The indices of the sheet's frames in the order the tag plays them.
+    /// Ping-pong tags are unrolled, since Sprites can only play forwards.
+    pub fn frame_order(&self) -> Vec<usize> {
+        let forward: Vec<usize> = (self.from..=self.to).collect();
+        let mut reverse = forward.clone();
+        reverse.reverse();
+
+        let bounce = |there: Vec<usize>, back: &[usize]| {
+            let inner = back.len().saturating_sub(1);
+            let mut output = there;
+            output.extend(back.iter().skip(1).take(inner.saturating_sub(1)));
+            output
+        };
+
+        match self.direction {
+            AsepriteDirection::Forward => forward,
+            AsepriteDirection::Reverse => reverse,
+            AsepriteDirection::PingPong => bounce(forward, &reverse),
+            AsepriteDirection::PingPongReverse => bounce(reverse, &forward),
+        }
+    }
+}
+
+/// The direction an Aseprite tag plays in.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum AsepriteDirection {
+    #[default]
+    Forward,
+    Reverse,
+    #[serde(rename = "pingpong")]
+    PingPong,
+    #[serde(rename = "pingpong_reverse")]
+    PingPongReverse,
+}
+
+impl AsepriteSheet {
+    /// Imports the sheet, creating one Sprite per tag by cropping each frame
+    /// out of the `sheet` image. Sprites are named `{base_name}_{tag_name}`,
+    /// with the tag name converted to snake case. If the sheet has no tags,
+    /// a single Sprite named `base_name` is made out of every frame.
+    ///
+    /// Per-frame durations are preserved by setting the playback speed and
+    /// stretching each keyframe's `length`; see
+    /// [`ImportedSprite::from_frames`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if a tag refers to frames which do not exist, if a frame does
+    /// not fit within the `sheet`, if the frames of a tag differ in size, or if
+    /// a Sprite's name would not be a valid resource name. Tags whose names
+    /// are the same in snake case, such as `Walk Left` and `walk_left`, are
+    /// an error rather than overwriting each other.
+    pub fn import(
+        &self,
+        base_name: &str,
+        sheet: &RgbaImage,
+    ) -> Result<Vec<ImportedSprite>, SpriteImportError> {
+        use heck::ToSnakeCase;
+
+        let untagged;
+        let tags: &[AsepriteTag] = if self.meta.frame_tags.is_empty() {
+            untagged = [AsepriteTag {
+                name: String::new(),
+                from: 0,
+                to: self.frames.len().saturating_sub(1),
+                direction: AsepriteDirection::Forward,
+            }];
+            &untagged
+        } else {
+            &self.meta.frame_tags
+        };
+
+        let validator = ResourceNameValidator::new();
+        let mut output: Vec<ImportedSprite> = Vec::with_capacity(tags.len());
+
+        for tag in tags {
+            let name = if tag.name.is_empty() {
+                base_name.to_string()
+            } else {
+                format!("{}_{}", base_name, tag.name.to_snake_case())
+            };
+            if !validator.is_valid(&name) {
+                return Err(SpriteImportError::InvalidName(name));
+            }
+            if output.iter().any(|v| v.sprite.common_data.name == name) {
+                return Err(SpriteImportError::DuplicateName(name));
+            }
+            if tag.from > tag.to || tag.to >= self.frames.len() {
+                return Err(SpriteImportError::TagOutOfRange(tag.name.clone()));
+            }
+
+            let order = tag.frame_order();
+            let mut images = Vec::with_capacity(order.len());
+            let mut durations = Vec::with_capacity(order.len());
+            for index in order {
+                let frame = &self.frames[index];
+                images.push(frame.extract(sheet)?);
+                if let Some(duration) = frame.duration {
+                    durations.push(duration);
+                }
+            }
+            if durations.len() != images.len() {
+                durations.clear();
+            }
+
+            output.push(ImportedSprite::from_frames(&name, images, &durations)?);
+        }
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const SHEET: &str = r##"{ "frames": {
+        "walk 0.aseprite": { "frame": { "x": 0, "y": 0, "w": 4, "h": 4 }, "rotated": false, "trimmed": false, "spriteSourceSize": { "x": 0, "y": 0, "w": 4, "h": 4 }, "sourceSize": { "w": 4, "h": 4 }, "duration": 100 },
+        "walk 1.aseprite": { "frame": { "x": 4, "y": 0, "w": 4, "h": 4 }, "rotated": false, "trimmed": false, "spriteSourceSize": { "x": 0, "y": 0, "w": 4, "h": 4 }, "sourceSize": { "w": 4, "h": 4 }, "duration": 300 },
+        "walk 2.aseprite": { "frame": { "x": 8, "y": 0, "w": 4, "h": 4 }, "rotated": false, "trimmed": false, "spriteSourceSize": { "x": 0, "y": 0, "w": 4, "h": 4 }, "sourceSize": { "w": 4, "h": 4 }, "duration": 100 }
+    },
+    "meta": {
+        "app": "https://www.aseprite.org/",
+        "version": "1.3.2",
+        "image": "walk.png",
+        "format": "RGBA8888",
+        "size": { "w": 12, "h": 4 },
+        "scale": "1",
+        "frameTags": [
+            { "name": "Idle North", "from": 0, "to": 0, "direction": "forward", "color": "#000000ff" },
+            { "name": "walk", "from": 0, "to": 2, "direction": "pingpong", "color": "#000000ff" }
+        ],
+        "layers": [ { "name": "Layer 1", "opacity": 255, "blendMode": "normal" } ],
+        "slices": []
+    }
+    }"##;
+
+    #[test]
+    fn import_tags() {
+        let sheet: AsepriteSheet = serde_json::from_str(SHEET).unwrap();
+        let mut image = RgbaImage::new(12, 4);
+        image.put_pixel(4, 0, image::Rgba([0, 255, 0, 255]));
+
+        let sprites = sheet.import("spr_player", &image).unwrap();
+        assert_eq!(sprites.len(), 2);
+
+        let idle = &sprites[0];
+        assert_eq!(idle.sprite.common_data.name, "spr_player_idle_north");
+        assert_eq!(idle.sprite.frames.len(), 1);
+        assert_eq!(idle.sprite.sequence.playback_speed, 10.0);
+
+        let walk = &sprites[1];
+        assert_eq!(walk.sprite.common_data.name, "spr_player_walk");
+        assert_eq!(walk.sprite.frames.len(), 4);
+        assert_eq!(walk.sprite.sequence.length, 8.0);
+        assert_eq!(
+            walk.images[1].get_pixel(0, 0),
+            &image::Rgba([0, 255, 0, 255])
+        );
+        assert_eq!(walk.images[3], walk.images[1]);
+
+        let mut clashing = sheet.clone();
+        clashing.meta.frame_tags[0].name = "Walk".to_string();
+        assert_eq!(
+            clashing.import("spr_player", &image).unwrap_err(),
+            SpriteImportError::DuplicateName("spr_player_walk".to_string())
+        );
+    }
+
+    #[test]
+    fn frame_orders() {
+        let mut tag = AsepriteTag {
+            name: "a".to_string(),
+            from: 1,
+            to: 4,
+            direction: AsepriteDirection::PingPong,
+        };
+        assert_eq!(tag.frame_order(), vec![1, 2, 3, 4, 3, 2]);
+
+        tag.direction = AsepriteDirection::PingPongReverse;
+        assert_eq!(tag.frame_order(), vec![4, 3, 2, 1, 2, 3]);
+
+        tag.direction = AsepriteDirection::Reverse;
+        assert_eq!(tag.frame_order(), vec![4, 3, 2, 1]);
+
+        tag.to = 1;
+        tag.direction = AsepriteDirection::PingPong;
+        assert_eq!(tag.frame_order(), vec![1]);
+    }
+}
@@ -0,0 +1,192 @@
+use super::{MessageChannels, MessageEventKeyframe, MessageKeyframe, Sprite};
+
+impl Sprite {
+    /// Adds a broadcast message which is sent when the animation reaches the
+    /// frame at index `frame`. Messages sent on the same frame share a
+    /// keyframe, and are sent in the order they were added. Adding a message
+    /// which the frame already sends does nothing.
+    ///
+    /// Returns `false` if the Sprite has no such frame.
+    pub fn add_broadcast_message(&mut self, frame: usize, message: &str) -> bool {
+        let Some(key) = self.frame_key(frame) else {
+            return false;
+        };
+
+        let keyframes = &mut self.sequence.events.keyframes;
+        match keyframes.iter_mut().find(|keyframe| keyframe.key == key) {
+            Some(keyframe) => {
+                let events = &mut keyframe.channels.zero.events;
+                if !events.iter().any(|event| event == message) {
+                    events.push(message.to_string());
+                }
+            }
+            None => {
+                let index = keyframes.partition_point(|keyframe| keyframe.key < key);
+                keyframes.insert(
+                    index,
+                    MessageKeyframe {
+                        channels: MessageChannels {
+                            zero: MessageEventKeyframe {
+                                events: vec![message.to_string()],
+                                ..Default::default()
+                            },
+                        },
+                        key,
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+
+        true
+    }
+
+    /// Lists every broadcast message of the Sprite along with the index of
+    /// the frame which sends it, in the order they are sent. Messages on
+    /// disabled keyframes are skipped, as are messages placed after the last
+    /// frame, which are never sent.
+    pub fn broadcast_messages(&self) -> Vec<(usize, &str)> {
+        let mut output = vec![];
+
+        for keyframe in self.sequence.events.keyframes.iter() {
+            if keyframe.disabled {
+                continue;
+            }
+            let Some(frame) = self.frame_index_at(keyframe.key) else {
+                continue;
+            };
+
+            for event in keyframe.channels.zero.events.iter() {
+                output.push((frame, event.as_str()));
+            }
+        }
+        output.sort_by_key(|(frame, _)| *frame);
+
+        output
+    }
+
+    /// Removes a broadcast message from the frame at index `frame`. Keyframes
+    /// are matched the same way as in
+    /// [`broadcast_messages`](Self::broadcast_messages), so a message on a
+    /// keyframe part of the way through the frame is removed too. If the
+    /// message was the last one on its keyframe, the keyframe is removed as
+    /// well.
+    ///
+    /// Returns `false` if the frame did not send the message.
+    pub fn remove_broadcast_message(&mut self, frame: usize, message: &str) -> bool {
+        let indices: Vec<usize> = self
+            .sequence
+            .events
+            .keyframes
+            .iter()
+            .enumerate()
+            .filter(|(_, keyframe)| {
+                !keyframe.disabled
+                    && self.frame_index_at(keyframe.key) == Some(frame)
+                    && keyframe.channels.zero.events.iter().any(|e| e == message)
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        let keyframes = &mut self.sequence.events.keyframes;
+        for index in indices.iter().rev() {
+            let events = &mut keyframes[*index].channels.zero.events;
+            events.retain(|event| event != message);
+            if events.is_empty() {
+                keyframes.remove(*index);
+            }
+        }
+
+        !indices.is_empty()
+    }
+
+    /// The `key` of the frame at index `frame` within the animation.
+    fn frame_key(&self, frame: usize) -> Option<f64> {
+        let mut keys: Vec<f64> = self
+            .frames_track()?
+            .keyframes
+            .keyframes
+            .iter()
+            .map(|keyframe| keyframe.key)
+            .collect();
+        keys.sort_by(f64::total_cmp);
+
+        keys.get(frame).copied()
+    }
+
+    /// The index of the frame which is shown at `key` within the animation.
+    fn frame_index_at(&self, key: f64) -> Option<usize> {
+        let mut keyframes: Vec<_> = self.frames_track()?.keyframes.keyframes.iter().collect();
+        keyframes.sort_by(|a, b| a.key.total_cmp(&b.key));
+
+        keyframes
+            .iter()
+            .position(|keyframe| keyframe.key <= key && key < keyframe.key + keyframe.length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::num::NonZeroUsize;
+
+    #[test]
+    fn broadcast_messages() {
+        let one = NonZeroUsize::new(1).unwrap();
+        let mut sprite = Sprite::new("spr_test", one, one);
+        sprite.push_frame(1.0);
+        sprite.push_frame(2.0);
+        sprite.push_frame(1.0);
+
+        assert!(sprite.add_broadcast_message(2, "footstep"));
+        assert!(sprite.add_broadcast_message(1, "footstep"));
+        assert!(sprite.add_broadcast_message(1, "dust"));
+        assert!(sprite.add_broadcast_message(1, "dust"));
+        assert!(!sprite.add_broadcast_message(3, "footstep"));
+
+        let keyframes = &sprite.sequence.events.keyframes;
+        assert_eq!(keyframes.len(), 2);
+        assert_eq!(keyframes[0].key, 1.0);
+        assert_eq!(keyframes[1].key, 3.0);
+        assert_eq!(
+            sprite.broadcast_messages(),
+            vec![(1, "footstep"), (1, "dust"), (2, "footstep")]
+        );
+
+        assert!(sprite.remove_broadcast_message(2, "footstep"));
+        assert!(!sprite.remove_broadcast_message(2, "footstep"));
+        assert!(sprite.remove_broadcast_message(1, "footstep"));
+        assert_eq!(sprite.broadcast_messages(), vec![(1, "dust")]);
+        assert_eq!(sprite.sequence.events.keyframes.len(), 1);
+
+        // frame 1 runs from 1.0 to 3.0, so a message at 2.5 belongs to it.
+        sprite.sequence.events.keyframes[0].key = 2.5;
+        assert_eq!(sprite.broadcast_messages(), vec![(1, "dust")]);
+        assert!(sprite.remove_broadcast_message(1, "dust"));
+        assert!(sprite.sequence.events.keyframes.is_empty());
+    }
+
+    #[test]
+    fn parse_messages() {
+        let json = r#"{
+            "$KeyframeStore<MessageEventKeyframe>": "",
+            "Keyframes": [
+                {"$Keyframe<MessageEventKeyframe>": "", "Channels": {
+                    "0": {"$MessageEventKeyframe": "", "Events": ["footstep"], "resourceType": "MessageEventKeyframe", "resourceVersion": "2.0"}
+                }, "Disabled": false, "id": "4c5ed9ae-8f15-4e3b-8fd8-d5c3a8c0a3c4", "IsCreationKey": false, "Key": 2.0, "Length": 1.0, "resourceType": "Keyframe<MessageEventKeyframe>", "resourceVersion": "2.0", "Stretch": false}
+            ],
+            "resourceType": "KeyframeStore<MessageEventKeyframe>",
+            "resourceVersion": "2.0"
+        }"#;
+
+        let events: crate::sprite::SpriteEvents = serde_json::from_str(json).unwrap();
+        assert_eq!(events.keyframes[0].channels.zero.events, vec!["footstep"]);
+
+        let value = serde_json::to_value(&events).unwrap();
+        assert_eq!(
+            value["Keyframes"][0]["Channels"]["0"]["resourceType"],
+            "MessageEventKeyframe"
+        );
+    }
+}
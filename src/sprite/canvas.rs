@@ -0,0 +1,476 @@
+use super::{BBoxMode, Origin, Rect, Sprite, SpriteImageError, SpriteType};
+use image::RgbaImage;
+use std::{num::NonZeroUsize, path::Path};
+
+/// Which part of the canvas stays in place when a Sprite's canvas is resized,
+/// as in the IDE's "Resize Canvas" dialog.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Anchor {
+    #[default]
+    TopLeft,
+    TopCenter,
+    TopRight,
+    MiddleLeft,
+    MiddleCenter,
+    MiddleRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl Anchor {
+    /// How far the old canvas moves when going from `old` to `new` pixels
+    /// wide (or tall), along the horizontal and vertical axis respectively.
+    fn offsets(self, old: (u32, u32), new: (u32, u32)) -> (i64, i64) {
+        let dx = i64::from(new.0) - i64::from(old.0);
+        let dy = i64::from(new.1) - i64::from(old.1);
+
+        let x = match self {
+            Anchor::TopLeft | Anchor::MiddleLeft | Anchor::BottomLeft => 0,
+            Anchor::TopCenter | Anchor::MiddleCenter | Anchor::BottomCenter => dx / 2,
+            Anchor::TopRight | Anchor::MiddleRight | Anchor::BottomRight => dx,
+        };
+        let y = match self {
+            Anchor::TopLeft | Anchor::TopCenter | Anchor::TopRight => 0,
+            Anchor::MiddleLeft | Anchor::MiddleCenter | Anchor::MiddleRight => dy / 2,
+            Anchor::BottomLeft | Anchor::BottomCenter | Anchor::BottomRight => dy,
+        };
+
+        (x, y)
+    }
+}
+
+/// A change to the size of a Sprite's canvas, as returned by
+/// [`Sprite::resize`], [`Sprite::crop`] and [`Sprite::scale`]. The Sprite's
+/// metadata has already been updated; the change is then applied to each of
+/// the Sprite's images with [`apply`](Self::apply), or to every image in the
+/// Sprite's directory with [`Sprite::apply_canvas_change`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct CanvasChange {
+    /// The size of the canvas before the change.
+    pub old_width: u32,
+    pub old_height: u32,
+
+    /// The size of the canvas after the change.
+    pub width: u32,
+    pub height: u32,
+
+    /// Where the old canvas's top left corner lands on the new canvas. This is
+    /// negative when pixels are cropped off the left or top.
+    pub x: i64,
+    pub y: i64,
+
+    /// Whether the image is stretched to the new size rather than moved.
+    pub scaled: bool,
+}
+
+/// A crop which is empty or does not fit within the Sprite.
+#[derive(Debug, thiserror::Error, PartialEq, Eq, Clone, Copy)]
+#[error("crop {0:?} is empty or does not fit within the sprite")]
+pub struct InvalidCrop(pub Rect);
+
+impl CanvasChange {
+    /// Applies the change to an image the size of the old canvas. Pixels
+    /// moved off the canvas are lost, and new pixels are transparent. Scaled
+    /// images use nearest-neighbour sampling, so pixel art stays crisp.
+    pub fn apply(&self, image: &RgbaImage) -> RgbaImage {
+        if self.scaled {
+            return image::imageops::resize(
+                image,
+                self.width,
+                self.height,
+                image::imageops::FilterType::Nearest,
+            );
+        }
+
+        let mut output = RgbaImage::new(self.width, self.height);
+        image::imageops::replace(&mut output, image, self.x, self.y);
+
+        output
+    }
+
+    /// Moves a horizontal pixel coordinate from the old canvas to the new one.
+    fn map_x(&self, x: i64) -> i64 {
+        Self::map(x, self.x, self.old_width, self.width, self.scaled)
+    }
+
+    /// Moves a vertical pixel coordinate from the old canvas to the new one.
+    fn map_y(&self, y: i64) -> i64 {
+        Self::map(y, self.y, self.old_height, self.height, self.scaled)
+    }
+
+    fn map(value: i64, offset: i64, old: u32, new: u32, scaled: bool) -> i64 {
+        if scaled {
+            (value as f64 * f64::from(new) / f64::from(old)).round() as i64
+        } else {
+            value + offset
+        }
+    }
+
+    /// Moves an inclusive range of pixels, clamping it to the new canvas. If
+    /// nothing of the range is left, the whole canvas is returned.
+    fn map_range(start: i64, end: i64, size: u32, map: impl Fn(i64) -> i64) -> (i32, i32) {
+        let last = i64::from(size) - 1;
+        let start = map(start).clamp(0, last);
+        let end = (map(end + 1) - 1).clamp(0, last);
+
+        if start > end {
+            (0, last as i32)
+        } else {
+            (start as i32, end as i32)
+        }
+    }
+
+    /// Moves an inset from the leading edge of the old canvas (the left or top
+    /// of a nine-slice) to the new canvas.
+    fn map_leading_inset(inset: u64, size: u32, map: impl Fn(i64) -> i64) -> u64 {
+        map(inset as i64).clamp(0, i64::from(size)) as u64
+    }
+
+    /// Moves an inset from the trailing edge of the old canvas (the right or
+    /// bottom of a nine-slice) to the new canvas.
+    fn map_trailing_inset(inset: u64, old: u32, new: u32, map: impl Fn(i64) -> i64) -> u64 {
+        let edge = map(i64::from(old) - inset as i64);
+
+        (i64::from(new) - edge).clamp(0, i64::from(new)) as u64
+    }
+}
+
+impl Sprite {
+    /// Resizes the Sprite's canvas without stretching its images. The
+    /// `anchor` decides which part of the old canvas stays in place; the
+    /// rest is padded with transparency or cut off.
+    ///
+    /// The bounding box, origin and nine-slice are updated to match. The
+    /// images on disk are not touched -- apply the returned change with
+    /// [`apply_canvas_change`](Self::apply_canvas_change).
+    pub fn resize(
+        &mut self,
+        width: NonZeroUsize,
+        height: NonZeroUsize,
+        anchor: Anchor,
+    ) -> CanvasChange {
+        let old = (self.width.get() as u32, self.height.get() as u32);
+        let new = (width.get() as u32, height.get() as u32);
+        let (x, y) = anchor.offsets(old, new);
+
+        let change = CanvasChange {
+            old_width: old.0,
+            old_height: old.1,
+            width: new.0,
+            height: new.1,
+            x,
+            y,
+            scaled: false,
+        };
+        self.apply_canvas_metadata(&change);
+
+        change
+    }
+
+    /// Crops the Sprite's canvas down to `rect`, which is in the Sprite's
+    /// current pixel coordinates.
+    ///
+    /// The bounding box, origin and nine-slice are updated to match. The
+    /// images on disk are not touched -- apply the returned change with
+    /// [`apply_canvas_change`](Self::apply_canvas_change).
+    ///
+    /// # Errors
+    ///
+    /// Errors if `rect` is empty or does not fit within the Sprite.
+    pub fn crop(&mut self, rect: Rect) -> Result<CanvasChange, InvalidCrop> {
+        let fits = rect.w != 0
+            && rect.h != 0
+            && rect
+                .x
+                .checked_add(rect.w)
+                .is_some_and(|r| r as usize <= self.width.get())
+            && rect
+                .y
+                .checked_add(rect.h)
+                .is_some_and(|b| b as usize <= self.height.get());
+        if !fits {
+            return Err(InvalidCrop(rect));
+        }
+
+        let change = CanvasChange {
+            old_width: self.width.get() as u32,
+            old_height: self.height.get() as u32,
+            width: rect.w,
+            height: rect.h,
+            x: -i64::from(rect.x),
+            y: -i64::from(rect.y),
+            scaled: false,
+        };
+        self.apply_canvas_metadata(&change);
+
+        Ok(change)
+    }
+
+    /// Scales the Sprite to a new size, stretching its images.
+    ///
+    /// The bounding box, custom origins and nine-slice are scaled by the same
+    /// amount, and preset origins are recomputed. The images on disk are not
+    /// touched -- apply the returned change with
+    /// [`apply_canvas_change`](Self::apply_canvas_change).
+    pub fn scale(&mut self, width: NonZeroUsize, height: NonZeroUsize) -> CanvasChange {
+        let change = CanvasChange {
+            old_width: self.width.get() as u32,
+            old_height: self.height.get() as u32,
+            width: width.get() as u32,
+            height: height.get() as u32,
+            x: 0,
+            y: 0,
+            scaled: true,
+        };
+        self.apply_canvas_metadata(&change);
+
+        change
+    }
+
+    /// Applies a canvas change to the composite image of every frame, and to
+    /// every layer image, in the Sprite's directory. This must be called
+    /// after the change was made to the Sprite, since images are checked
+    /// against the Sprite's new size when they are written.
+    ///
+    /// Every image is read and changed before any is written, so an image
+    /// which cannot be read leaves the directory untouched. A Sprite without
+    /// a `layers` directory is treated as having no layer images. Sprites
+    /// which are not bitmaps have no frame images, so nothing is done for
+    /// them.
+    ///
+    /// # Errors
+    ///
+    /// Errors if an image cannot be read or written.
+    pub fn apply_canvas_change(
+        &self,
+        sprite_dir: &Path,
+        change: &CanvasChange,
+    ) -> Result<(), SpriteImageError> {
+        if self.resource_sprite_type != SpriteType::Bitmap {
+            return Ok(());
+        }
+        let has_layers = sprite_dir.join("layers").is_dir();
+
+        let mut frames = Vec::with_capacity(self.frames.len());
+        let mut layers = vec![];
+        for frame in &self.frames {
+            let frame = frame.name;
+
+            let image = Self::read_frame_image(sprite_dir, frame)?;
+            frames.push((frame, change.apply(&image)));
+
+            if has_layers {
+                for layer in &self.layers {
+                    let layer = layer.common_data.name;
+
+                    let image = Self::read_layer_image(sprite_dir, frame, layer)?;
+                    layers.push((frame, layer, change.apply(&image)));
+                }
+            }
+        }
+
+        for (_, image) in frames.iter() {
+            self.check_image_size(image)?;
+        }
+        for (frame, image) in frames {
+            image.save(sprite_dir.join(Self::frame_image_path(frame)))?;
+        }
+        for (frame, layer, image) in layers {
+            self.write_layer_image(sprite_dir, frame, layer, &image)?;
+        }
+
+        Ok(())
+    }
+
+    fn apply_canvas_metadata(&mut self, change: &CanvasChange) {
+        let map_x = |x| change.map_x(x);
+        let map_y = |y| change.map_y(y);
+
+        if self.bbox_mode == BBoxMode::FullImage {
+            self.bbox_left = 0;
+            self.bbox_top = 0;
+            self.bbox_right = change.width as i32 - 1;
+            self.bbox_bottom = change.height as i32 - 1;
+        } else {
+            (self.bbox_left, self.bbox_right) = CanvasChange::map_range(
+                self.bbox_left.into(),
+                self.bbox_right.into(),
+                change.width,
+                map_x,
+            );
+            (self.bbox_top, self.bbox_bottom) = CanvasChange::map_range(
+                self.bbox_top.into(),
+                self.bbox_bottom.into(),
+                change.height,
+                map_y,
+            );
+        }
+
+        if let Some(nine_slice) = &mut self.nine_slice {
+            nine_slice.left = CanvasChange::map_leading_inset(nine_slice.left, change.width, map_x);
+            nine_slice.top = CanvasChange::map_leading_inset(nine_slice.top, change.height, map_y);
+            nine_slice.right = CanvasChange::map_trailing_inset(
+                nine_slice.right,
+                change.old_width,
+                change.width,
+                map_x,
+            );
+            nine_slice.bottom = CanvasChange::map_trailing_inset(
+                nine_slice.bottom,
+                change.old_height,
+                change.height,
+                map_y,
+            );
+        }
+
+        self.width = NonZeroUsize::new(change.width as usize).unwrap();
+        self.height = NonZeroUsize::new(change.height as usize).unwrap();
+
+        if self.origin == Origin::Custom {
+            self.sequence.xorigin = map_x(self.sequence.xorigin.into()) as i32;
+            self.sequence.yorigin = map_y(self.sequence.yorigin.into()) as i32;
+        } else {
+            self.update_origin();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprite::NineSlice;
+    use pretty_assertions::assert_eq;
+
+    fn size(value: usize) -> NonZeroUsize {
+        NonZeroUsize::new(value).unwrap()
+    }
+
+    fn sprite() -> Sprite {
+        let mut sprite = Sprite::new("spr_test", size(8), size(8));
+        sprite.bbox_mode = BBoxMode::Manual;
+        sprite.bbox_left = 2;
+        sprite.bbox_top = 2;
+        sprite.bbox_right = 5;
+        sprite.bbox_bottom = 5;
+        sprite.set_custom_origin(4, 6);
+        sprite.nine_slice = Some(NineSlice {
+            left: 2,
+            top: 2,
+            right: 2,
+            bottom: 2,
+            ..Default::default()
+        });
+
+        sprite
+    }
+
+    #[test]
+    fn resize_canvas() {
+        let mut sprite = sprite();
+        let change = sprite.resize(size(12), size(10), Anchor::MiddleCenter);
+        assert_eq!((change.x, change.y), (2, 1));
+        assert_eq!((sprite.width.get(), sprite.height.get()), (12, 10));
+        assert_eq!((sprite.bbox_left, sprite.bbox_right), (4, 7));
+        assert_eq!((sprite.bbox_top, sprite.bbox_bottom), (3, 6));
+        assert_eq!(sprite.origin_point(), (6, 7));
+
+        let nine_slice = sprite.nine_slice.as_ref().unwrap();
+        assert_eq!((nine_slice.left, nine_slice.right), (4, 4));
+        assert_eq!((nine_slice.top, nine_slice.bottom), (3, 3));
+
+        let mut image = RgbaImage::new(8, 8);
+        image.put_pixel(0, 0, image::Rgba([255, 0, 0, 255]));
+        let image = change.apply(&image);
+        assert_eq!(image.dimensions(), (12, 10));
+        assert_eq!(image.get_pixel(2, 1), &image::Rgba([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn crop_canvas() {
+        let mut sprite = sprite();
+        sprite.set_origin(Origin::BottomRight);
+
+        let change = sprite
+            .crop(Rect {
+                x: 3,
+                y: 0,
+                w: 5,
+                h: 4,
+            })
+            .unwrap();
+        assert_eq!((change.x, change.y), (-3, 0));
+        assert_eq!((sprite.bbox_left, sprite.bbox_right), (0, 2));
+        assert_eq!((sprite.bbox_top, sprite.bbox_bottom), (2, 3));
+        assert_eq!(sprite.origin_point(), (4, 3));
+
+        let nine_slice = sprite.nine_slice.as_ref().unwrap();
+        assert_eq!((nine_slice.left, nine_slice.right), (0, 2));
+        assert_eq!((nine_slice.top, nine_slice.bottom), (2, 0));
+
+        let crop = Rect {
+            x: 1,
+            y: 0,
+            w: 5,
+            h: 1,
+        };
+        assert_eq!(sprite.crop(crop), Err(InvalidCrop(crop)));
+    }
+
+    #[test]
+    fn scale_canvas() {
+        let mut sprite = sprite();
+        let change = sprite.scale(size(16), size(4));
+        assert_eq!((sprite.bbox_left, sprite.bbox_right), (4, 11));
+        assert_eq!((sprite.bbox_top, sprite.bbox_bottom), (1, 2));
+        assert_eq!(sprite.origin_point(), (8, 3));
+
+        let nine_slice = sprite.nine_slice.as_ref().unwrap();
+        assert_eq!((nine_slice.left, nine_slice.right), (4, 4));
+        assert_eq!((nine_slice.top, nine_slice.bottom), (1, 1));
+
+        let mut image = RgbaImage::new(8, 8);
+        image.put_pixel(7, 7, image::Rgba([255, 0, 0, 255]));
+        let image = change.apply(&image);
+        assert_eq!(image.get_pixel(15, 3), &image::Rgba([255, 0, 0, 255]));
+        assert_eq!(image.get_pixel(13, 3), &image::Rgba([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn apply_canvas_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut sprite = Sprite::new("spr_test", size(2), size(2));
+        let frames = [sprite.push_frame(1.0), sprite.push_frame(1.0)];
+        for frame in frames {
+            sprite
+                .write_frame_image(dir.path(), frame, &RgbaImage::new(2, 2))
+                .unwrap();
+        }
+        let layer = sprite.layers[0].common_data.name;
+
+        // a missing layer image stops every image being changed.
+        let missing = dir.path().join(Sprite::layer_image_path(frames[1], layer));
+        std::fs::rename(&missing, dir.path().join("missing.png")).unwrap();
+        let change = sprite.resize(size(4), size(3), Anchor::TopLeft);
+        assert!(sprite.apply_canvas_change(dir.path(), &change).is_err());
+        let image = Sprite::read_frame_image(dir.path(), frames[0]).unwrap();
+        assert_eq!(image.dimensions(), (2, 2));
+
+        std::fs::rename(dir.path().join("missing.png"), &missing).unwrap();
+        sprite.apply_canvas_change(dir.path(), &change).unwrap();
+        for frame in frames {
+            let image = Sprite::read_frame_image(dir.path(), frame).unwrap();
+            assert_eq!(image.dimensions(), (4, 3));
+            let image = Sprite::read_layer_image(dir.path(), frame, layer).unwrap();
+            assert_eq!(image.dimensions(), (4, 3));
+        }
+
+        // without a layers directory, only the composite images change.
+        std::fs::remove_dir_all(dir.path().join("layers")).unwrap();
+        let change = sprite.resize(size(5), size(5), Anchor::TopLeft);
+        sprite.apply_canvas_change(dir.path(), &change).unwrap();
+        let image = Sprite::read_frame_image(dir.path(), frames[1]).unwrap();
+        assert_eq!(image.dimensions(), (5, 5));
+        assert!(!dir.path().join("layers").exists());
+    }
+}
@@ -0,0 +1,201 @@
+use super::{BlendMode, FrameId, Sprite, SpriteImageError, SpriteLayer};
+use image::{Rgba, RgbaImage};
+use std::path::Path;
+
+impl Sprite {
+    /// Returns the image of a frame as the IDE shows it.
+    ///
+    /// Single layer Sprites use the frame's composite image. Sprites with
+    /// more than one layer have their layer images flattened instead, since
+    /// the composite image may be stale after a layer was edited; see
+    /// [`flatten_frame`](Self::flatten_frame).
+    ///
+    /// # Errors
+    ///
+    /// Errors if the Sprite is not a bitmap Sprite, or if an image cannot be
+    /// read, or is not the size of the Sprite.
+    pub fn composite_frame(
+        &self,
+        sprite_dir: &Path,
+        frame: FrameId,
+    ) -> Result<RgbaImage, SpriteImageError> {
+        self.check_bitmap()?;
+
+        if self.layers.len() <= 1 {
+            Self::read_frame_image(sprite_dir, frame)
+        } else {
+            self.flatten_frame(sprite_dir, frame)
+        }
+    }
+
+    /// Flattens the layer images of a frame in the Sprite's directory into a
+    /// single image; see [`composite_layers`](Self::composite_layers).
+    ///
+    /// # Errors
+    ///
+    /// Errors if the Sprite is not a bitmap Sprite, or if a layer image cannot
+    /// be read, or is not the size of the Sprite.
+    pub fn flatten_frame(
+        &self,
+        sprite_dir: &Path,
+        frame: FrameId,
+    ) -> Result<RgbaImage, SpriteImageError> {
+        self.check_bitmap()?;
+
+        let mut images = Vec::with_capacity(self.layers.len());
+        for layer in self.layers.iter().filter(|layer| layer.visible) {
+            let image = Self::read_layer_image(sprite_dir, frame, layer.common_data.name)?;
+            self.check_image_size(&image)?;
+            images.push((layer, image));
+        }
+
+        Ok(self.composite_layers(images.iter().map(|(layer, image)| (*layer, image))))
+    }
+
+    /// Regenerates the composite image of a frame out of its layer images,
+    /// as the IDE does after a layer is edited. Tools which edit layer
+    /// images should call this afterwards, since the composite image is what
+    /// the runtime uses.
+    ///
+    /// # Errors
+    ///
+    /// Errors if a layer image cannot be read, is not the size of the Sprite,
+    /// or if the composite image cannot be written.
+    pub fn write_composite(
+        &self,
+        sprite_dir: &Path,
+        frame: FrameId,
+    ) -> Result<(), SpriteImageError> {
+        let image = self.flatten_frame(sprite_dir, frame)?;
+        image.save(sprite_dir.join(Self::frame_image_path(frame)))?;
+
+        Ok(())
+    }
+
+    /// Flattens the images of a frame's layers into a single image the size
+    /// of the Sprite. Layers are given top-most first, in the same order as
+    /// `self.layers`, and are drawn with their `opacity` and `blend_mode`.
+    /// Layers which are not `visible` are skipped.
+    ///
+    /// Every image must be the size of the Sprite.
+    pub fn composite_layers<'a>(
+        &self,
+        layers: impl DoubleEndedIterator<Item = (&'a SpriteLayer, &'a RgbaImage)>,
+    ) -> RgbaImage {
+        let mut output = RgbaImage::new(self.width.get() as u32, self.height.get() as u32);
+
+        for (layer, image) in layers.rev().filter(|(layer, _)| layer.visible) {
+            let opacity = (layer.opacity / 100.0).clamp(0.0, 1.0) as f32;
+            let mode = layer.blend_mode;
+
+            for (backdrop, source) in output.pixels_mut().zip(image.pixels()) {
+                blend(backdrop, *source, opacity, mode);
+            }
+        }
+
+        output
+    }
+}
+
+/// Draws `source` over `backdrop`, using the separable blend modes of the
+/// W3C compositing spec with source-over compositing.
+fn blend(backdrop: &mut Rgba<u8>, source: Rgba<u8>, opacity: f32, mode: BlendMode) {
+    let source_alpha = f32::from(source[3]) / 255.0 * opacity;
+    if source_alpha <= 0.0 {
+        return;
+    }
+    let backdrop_alpha = f32::from(backdrop[3]) / 255.0;
+    let alpha = source_alpha + backdrop_alpha * (1.0 - source_alpha);
+
+    for channel in 0..3 {
+        let cs = f32::from(source[channel]) / 255.0;
+        let cb = f32::from(backdrop[channel]) / 255.0;
+
+        let blended = match mode {
+            BlendMode::Normal => cs,
+            BlendMode::Add => (cb + cs).min(1.0),
+            BlendMode::Subtract => (cb - cs).max(0.0),
+            BlendMode::Multiply => cb * cs,
+        };
+        let cs = (1.0 - backdrop_alpha) * cs + backdrop_alpha * blended;
+        let output = source_alpha * cs + (1.0 - source_alpha) * backdrop_alpha * cb;
+
+        backdrop[channel] = (output / alpha * 255.0).round() as u8;
+    }
+    backdrop[3] = (alpha * 255.0).round() as u8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprite::LayerId;
+    use pretty_assertions::assert_eq;
+    use std::num::NonZeroUsize;
+
+    #[test]
+    fn composite_layers() {
+        let one = NonZeroUsize::new(1).unwrap();
+        let sprite = Sprite::new("spr_test", one, one);
+        let layer = |blend_mode, opacity, visible| SpriteLayer {
+            common_data: crate::CommonData::new(LayerId::new()),
+            blend_mode,
+            opacity,
+            visible,
+            ..Default::default()
+        };
+        let pixel = |r, g, b, a| RgbaImage::from_pixel(1, 1, Rgba([r, g, b, a]));
+
+        let top = layer(BlendMode::Normal, 50.0, true);
+        let hidden = layer(BlendMode::Normal, 100.0, false);
+        let bottom = layer(BlendMode::Normal, 100.0, true);
+        let red = pixel(255, 0, 0, 255);
+        let green = pixel(0, 255, 0, 255);
+        let blue = pixel(0, 0, 255, 255);
+
+        let output = sprite
+            .composite_layers([(&top, &red), (&hidden, &green), (&bottom, &blue)].into_iter());
+        assert_eq!(output.get_pixel(0, 0), &Rgba([128, 0, 128, 255]));
+
+        let multiply = layer(BlendMode::Multiply, 100.0, true);
+        let grey = pixel(128, 128, 128, 255);
+        let output = sprite.composite_layers([(&multiply, &grey), (&bottom, &blue)].into_iter());
+        assert_eq!(output.get_pixel(0, 0), &Rgba([0, 0, 128, 255]));
+
+        let add = layer(BlendMode::Add, 100.0, true);
+        let output = sprite.composite_layers([(&add, &red), (&bottom, &blue)].into_iter());
+        assert_eq!(output.get_pixel(0, 0), &Rgba([255, 0, 255, 255]));
+
+        let output = sprite.composite_layers([(&multiply, &grey)].into_iter());
+        assert_eq!(output.get_pixel(0, 0), &Rgba([128, 128, 128, 255]));
+    }
+
+    #[test]
+    fn write_composite() {
+        let dir = tempfile::tempdir().unwrap();
+        let one = NonZeroUsize::new(1).unwrap();
+        let mut sprite = Sprite::new("spr_test", one, one);
+        sprite.layers.insert(
+            0,
+            SpriteLayer {
+                common_data: crate::CommonData::new(LayerId::new()),
+                blend_mode: BlendMode::Subtract,
+                visible: true,
+                ..Default::default()
+            },
+        );
+        let frame = sprite.push_frame(1.0);
+
+        let top = RgbaImage::from_pixel(1, 1, Rgba([100, 0, 0, 255]));
+        let bottom = RgbaImage::from_pixel(1, 1, Rgba([255, 255, 0, 255]));
+        for (layer, image) in [(0, &top), (1, &bottom)] {
+            let layer = sprite.layers[layer].common_data.name;
+            sprite
+                .write_layer_image(dir.path(), frame, layer, image)
+                .unwrap();
+        }
+
+        sprite.write_composite(dir.path(), frame).unwrap();
+        let image = Sprite::read_frame_image(dir.path(), frame).unwrap();
+        assert_eq!(image.get_pixel(0, 0), &Rgba([155, 255, 0, 255]));
+    }
+}
@@ -0,0 +1,397 @@
+use super::{FrameId, Sprite, SpriteImageError, SpriteType};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+/// Frames of a single Sprite whose images are identical.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DuplicateFrames {
+    /// The name of the Sprite.
+    pub sprite: String,
+
+    /// The identical frames, in the order they appear in the Sprite. The
+    /// first is the one which [`Sprite::dedupe_frames`] keeps.
+    pub frames: Vec<FrameId>,
+}
+
+/// The result of [`find_duplicates`].
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct DuplicateReport {
+    /// Every group of identical frames within a single Sprite.
+    pub duplicate_frames: Vec<DuplicateFrames>,
+
+    /// Every group of Sprites which are the same size and have identical
+    /// frames, in the same order. Each group is a list of Sprite names.
+    pub duplicate_sprites: Vec<Vec<String>>,
+
+    /// The number of pixels on texture pages taken up by duplicates: every
+    /// frame but the first of each group of identical frames, and every
+    /// distinct frame of every Sprite but the first of each group of
+    /// identical Sprites.
+    pub wasted_area: u64,
+}
+
+/// Hashes the frame images of every Sprite, each given with its directory,
+/// and reports identical frames within a Sprite and identical Sprites.
+///
+/// Frames within a Sprite with the same hash are compared pixel by pixel.
+/// Sprites are compared only by the hashes of their frames, so that a whole
+/// project does not need to be kept in memory; two different Sprites could
+/// be reported as identical if their hashes collide, which is very unlikely.
+/// Sprites which are not bitmaps are skipped.
+///
+/// # Errors
+///
+/// Errors if a frame image cannot be read.
+pub fn find_duplicates<'a>(
+    sprites: impl IntoIterator<Item = (&'a Sprite, &'a Path)>,
+) -> Result<DuplicateReport, SpriteImageError> {
+    let mut report = DuplicateReport::default();
+    let mut sprite_groups: HashMap<SpriteKey, Vec<(String, u64)>> = HashMap::new();
+    let mut sprite_order = vec![];
+
+    for (sprite, sprite_dir) in sprites {
+        if sprite.resource_sprite_type != SpriteType::Bitmap {
+            continue;
+        }
+        let area = (sprite.width.get() * sprite.height.get()) as u64;
+        let hashes = sprite.frame_hashes(sprite_dir)?;
+
+        let groups = sprite.identical_frames(sprite_dir, &hashes)?;
+        for frames in groups.iter().filter(|frames| frames.len() > 1) {
+            report.wasted_area += (frames.len() as u64 - 1) * area;
+            report.duplicate_frames.push(DuplicateFrames {
+                sprite: sprite.common_data.name.clone(),
+                frames: frames.clone(),
+            });
+        }
+
+        let key = (
+            sprite.width.get(),
+            sprite.height.get(),
+            hashes.iter().map(|(_, hash)| *hash).collect(),
+        );
+        let distinct_area = groups.len() as u64 * area;
+        let group = sprite_groups.entry(key).or_insert_with_key(|key| {
+            sprite_order.push(key.clone());
+            vec![]
+        });
+        group.push((sprite.common_data.name.clone(), distinct_area));
+    }
+
+    for key in sprite_order {
+        let group = &sprite_groups[&key];
+        if group.len() > 1 {
+            report.wasted_area += group.iter().skip(1).map(|(_, area)| area).sum::<u64>();
+            report
+                .duplicate_sprites
+                .push(group.iter().map(|(name, _)| name.clone()).collect());
+        }
+    }
+
+    Ok(report)
+}
+
+/// A Sprite's width, height, and the hash of each of its frames, in order.
+type SpriteKey = (usize, usize, Vec<u64>);
+
+/// Groups frames by their hash, keeping the order in which each hash first
+/// appears.
+fn group_frames(hashes: &[(FrameId, u64)]) -> Vec<Vec<FrameId>> {
+    let mut groups: Vec<(u64, Vec<FrameId>)> = vec![];
+    for (frame, hash) in hashes {
+        match groups.iter_mut().find(|(h, _)| h == hash) {
+            Some((_, frames)) => frames.push(*frame),
+            None => groups.push((*hash, vec![*frame])),
+        }
+    }
+
+    groups.into_iter().map(|(_, frames)| frames).collect()
+}
+
+/// The 64-bit FNV-1a hash of `bytes`, continuing from `hash`.
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+
+    hash
+}
+
+impl Sprite {
+    /// Hashes the image of each frame of the Sprite, in the order of
+    /// `self.frames`. Identical images have identical hashes, but different
+    /// images can share a hash, so a matching hash only makes frames
+    /// candidates for being identical.
+    ///
+    /// The hash is the 64-bit FNV-1a hash of the image's width and height, as
+    /// little-endian `u32`s, followed by its RGBA pixels, row by row. It is
+    /// the same on every platform and in every version of this crate, so it
+    /// can be stored and compared later.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the Sprite is not a bitmap Sprite, or if a frame image
+    /// cannot be read.
+    pub fn frame_hashes(&self, sprite_dir: &Path) -> Result<Vec<(FrameId, u64)>, SpriteImageError> {
+        self.frames
+            .iter()
+            .map(|frame| {
+                let image = self.composite_frame(sprite_dir, frame.name)?;
+
+                let mut hash = 0xcbf2_9ce4_8422_2325;
+                hash = fnv1a(hash, &image.width().to_le_bytes());
+                hash = fnv1a(hash, &image.height().to_le_bytes());
+                hash = fnv1a(hash, image.as_raw());
+
+                Ok((frame.name, hash))
+            })
+            .collect()
+    }
+
+    /// Groups the frames given by `hashes` into frames with identical
+    /// images, in the order each image first appears. Frames which share a
+    /// hash are compared pixel by pixel, so a hash collision never groups
+    /// different images together.
+    fn identical_frames(
+        &self,
+        sprite_dir: &Path,
+        hashes: &[(FrameId, u64)],
+    ) -> Result<Vec<Vec<FrameId>>, SpriteImageError> {
+        let mut output = vec![];
+        for frames in group_frames(hashes) {
+            if frames.len() == 1 {
+                output.push(frames);
+                continue;
+            }
+
+            let mut groups: Vec<(image::RgbaImage, Vec<FrameId>)> = vec![];
+            for frame in frames {
+                let image = self.composite_frame(sprite_dir, frame)?;
+                match groups.iter_mut().find(|(other, _)| *other == image) {
+                    Some((_, frames)) => frames.push(frame),
+                    None => groups.push((image, vec![frame])),
+                }
+            }
+            output.extend(groups.into_iter().map(|(_, frames)| frames));
+        }
+        output.sort_by_key(|frames| hashes.iter().position(|(frame, _)| *frame == frames[0]));
+
+        Ok(output)
+    }
+
+    /// Removes frames whose images are identical, pixel for pixel, to an
+    /// earlier frame, pointing the keyframes which showed them at that
+    /// earlier frame instead. The animation looks exactly the same, but the
+    /// Sprite takes up less space on its texture page.
+    ///
+    /// The images of the removed frames are deleted from the Sprite's
+    /// directory. Returns the Ids of the removed frames.
+    ///
+    /// The images are moved out of the way before the Sprite is changed, so
+    /// on an error both the Sprite and its directory are left as they were.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the Sprite is not a bitmap Sprite, or if a frame image
+    /// cannot be read or moved.
+    pub fn dedupe_frames(&mut self, sprite_dir: &Path) -> Result<Vec<FrameId>, SpriteImageError> {
+        let hashes = self.frame_hashes(sprite_dir)?;
+
+        let mut replacements = HashMap::new();
+        for frames in self.identical_frames(sprite_dir, &hashes)? {
+            for duplicate in &frames[1..] {
+                replacements.insert(*duplicate, frames[0]);
+            }
+        }
+        if replacements.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let removed: Vec<FrameId> = self
+            .frames
+            .iter()
+            .map(|frame| frame.name)
+            .filter(|frame| replacements.contains_key(frame))
+            .collect();
+
+        // the images are moved aside first, so that if any cannot be, the
+        // others can be put back before the Sprite is changed.
+        let mut moved: Vec<(PathBuf, PathBuf)> = vec![];
+        for frame in removed.iter() {
+            let mut paths = vec![sprite_dir.join(Self::frame_image_path(*frame))];
+            let layers_dir = sprite_dir.join("layers").join(frame.inner().to_string());
+            if layers_dir.is_dir() {
+                paths.push(layers_dir);
+            }
+
+            for path in paths {
+                let mut file_name = std::ffi::OsString::from(".");
+                file_name.push(path.file_name().unwrap_or_default());
+                file_name.push(".deleted");
+                let aside = path.with_file_name(file_name);
+
+                if let Err(e) = std::fs::rename(&path, &aside) {
+                    for (path, aside) in moved.iter().rev() {
+                        let _ = std::fs::rename(aside, path);
+                    }
+                    return Err(e.into());
+                }
+                moved.push((path, aside));
+            }
+        }
+
+        if let Some(track) = self.frames_track_mut() {
+            for keyframe in track.keyframes.keyframes.iter_mut() {
+                if let Some(kept) = keyframe.frame_id().and_then(|f| replacements.get(&f)) {
+                    keyframe.channels.zero.id.name = kept.inner().to_string();
+                }
+            }
+        }
+
+        self.frames
+            .retain(|frame| !replacements.contains_key(&frame.name));
+
+        // the Sprite no longer refers to these, so failing to delete one only
+        // leaves a hidden file behind.
+        for (_, aside) in moved {
+            let _ = if aside.is_dir() {
+                std::fs::remove_dir_all(aside)
+            } else {
+                std::fs::remove_file(aside)
+            };
+        }
+
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+    use pretty_assertions::assert_eq;
+    use std::num::NonZeroUsize;
+
+    fn sprite(dir: &Path, name: &str, colours: &[u8]) -> Sprite {
+        let two = NonZeroUsize::new(2).unwrap();
+        let mut sprite = Sprite::new(name, two, two);
+        for colour in colours {
+            let frame = sprite.push_frame(1.0);
+            let image = RgbaImage::from_pixel(2, 2, Rgba([*colour, 0, 0, 255]));
+            sprite.write_frame_image(dir, frame, &image).unwrap();
+        }
+
+        sprite
+    }
+
+    #[test]
+    fn find_duplicates() {
+        let dirs: Vec<_> = (0..3).map(|_| tempfile::tempdir().unwrap()).collect();
+        let a = sprite(dirs[0].path(), "spr_a", &[1, 2, 1]);
+        let b = sprite(dirs[1].path(), "spr_b", &[3]);
+        let c = sprite(dirs[2].path(), "spr_c", &[1, 2, 1]);
+
+        let report = super::find_duplicates([
+            (&a, dirs[0].path()),
+            (&b, dirs[1].path()),
+            (&c, dirs[2].path()),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            report.duplicate_frames,
+            vec![
+                DuplicateFrames {
+                    sprite: "spr_a".to_string(),
+                    frames: vec![a.frames[0].name, a.frames[2].name],
+                },
+                DuplicateFrames {
+                    sprite: "spr_c".to_string(),
+                    frames: vec![c.frames[0].name, c.frames[2].name],
+                }
+            ]
+        );
+        assert_eq!(
+            report.duplicate_sprites,
+            vec![vec!["spr_a".to_string(), "spr_c".to_string()]]
+        );
+        assert_eq!(report.wasted_area, 4 + 4 + 8);
+    }
+
+    #[test]
+    fn frame_hashes() {
+        let dir = tempfile::tempdir().unwrap();
+        let sprite = sprite(dir.path(), "spr_a", &[1]);
+
+        // this value must never change, as hashes may be stored.
+        let hashes = sprite.frame_hashes(dir.path()).unwrap();
+        assert_eq!(hashes[0].1, 0xa2a8a6d04538e065);
+    }
+
+    #[test]
+    fn hash_collisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let sprite = sprite(dir.path(), "spr_a", &[1, 2, 1, 3]);
+        let frames: Vec<_> = sprite.frames.iter().map(|frame| frame.name).collect();
+
+        // pretend every frame has the same hash.
+        let hashes: Vec<_> = frames.iter().map(|frame| (*frame, 0)).collect();
+        assert_eq!(
+            sprite.identical_frames(dir.path(), &hashes).unwrap(),
+            vec![vec![frames[0], frames[2]], vec![frames[1]], vec![frames[3]]]
+        );
+    }
+
+    #[test]
+    fn dedupe_frames() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut sprite = sprite(dir.path(), "spr_a", &[1, 2, 1, 1]);
+        let frames: Vec<_> = sprite.frames.iter().map(|frame| frame.name).collect();
+
+        let removed = sprite.dedupe_frames(dir.path()).unwrap();
+        assert_eq!(removed, vec![frames[2], frames[3]]);
+        assert_eq!(sprite.frames.len(), 2);
+        assert!(
+            !dir.path()
+                .join(Sprite::frame_image_path(frames[2]))
+                .exists()
+        );
+
+        let shown: Vec<_> = sprite
+            .frames_track()
+            .unwrap()
+            .keyframes
+            .keyframes
+            .iter()
+            .map(|keyframe| keyframe.frame_id().unwrap())
+            .collect();
+        assert_eq!(shown, vec![frames[0], frames[1], frames[0], frames[0]]);
+        assert_eq!(sprite.sequence.length, 4.0);
+        let leftovers = std::fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(leftovers, 3);
+    }
+
+    #[test]
+    fn dedupe_frames_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut sprite = sprite(dir.path(), "spr_a", &[1, 2, 1, 1]);
+        let original = sprite.clone();
+        let frames: Vec<_> = sprite.frames.iter().map(|frame| frame.name).collect();
+
+        // the last duplicate cannot be moved aside, as something is in the way.
+        let mut blocker = std::ffi::OsString::from(".");
+        blocker.push(Sprite::frame_image_path(frames[3]));
+        blocker.push(".deleted");
+        std::fs::create_dir(dir.path().join(&blocker)).unwrap();
+        std::fs::write(dir.path().join(&blocker).join("file"), "").unwrap();
+
+        assert!(sprite.dedupe_frames(dir.path()).is_err());
+        assert_eq!(sprite, original);
+        for frame in frames {
+            assert!(dir.path().join(Sprite::frame_image_path(frame)).exists());
+        }
+    }
+}
@@ -46,9 +46,9 @@ pub struct SpriteLayer {
     #[serde(flatten)]
     pub common_data: crate::CommonData<consts::ImageLayer, LayerId>,
 
-    /// Defines the blendmode in the GMS2 editor. @todo Must be typed at a later
-    /// date.
-    pub blend_mode: usize,
+    /// Defines how the layer is blended onto the layers below it when the
+    /// composite image is made.
+    pub blend_mode: BlendMode,
 
     /// This is the actual name shown in the GMS2 Sprite Editor.
     #[default("default".to_string())]
@@ -67,6 +67,7 @@ pub struct SpriteLayer {
     pub visible: bool,
 }
 
+/// The blend modes which the GMS2 Sprite Editor offers for layers.
 #[derive(
     Serialize_repr,
     Deserialize_repr,
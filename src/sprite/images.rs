@@ -0,0 +1,147 @@
+use super::{FrameId, LayerId, Sprite, SpriteType};
+use image::RgbaImage;
+use std::path::{Path, PathBuf};
+
+/// Errors which can occur when reading or writing the images of a Sprite.
+#[derive(Debug, thiserror::Error)]
+pub enum SpriteImageError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+    #[error(transparent)]
+    Png(#[from] png::EncodingError),
+    #[error(transparent)]
+    Gif(#[from] gif::EncodingError),
+    #[error("image was {0}x{1}, but the sprite is {2}x{3}")]
+    WrongSize(u32, u32, usize, usize),
+    #[error("sprite is too large to export")]
+    TooLarge,
+    #[error("sprite has no frames to export")]
+    NoFrames,
+    #[error("{0:?} sprites do not have frame images")]
+    NotBitmap(SpriteType),
+}
+
+impl Sprite {
+    /// The path to the composite image of a frame, relative to the Sprite's
+    /// directory. This is the image which the IDE and the runtime use.
+    pub fn frame_image_path(frame: FrameId) -> PathBuf {
+        PathBuf::from(format!("{}.png", frame.inner()))
+    }
+
+    /// The path to the image of a single layer within a frame, relative to the
+    /// Sprite's directory.
+    pub fn layer_image_path(frame: FrameId, layer: LayerId) -> PathBuf {
+        Path::new("layers")
+            .join(frame.inner().to_string())
+            .join(format!("{}.png", layer.inner()))
+    }
+
+    /// Reads the composite image of a frame from the Sprite's directory.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the image cannot be read or decoded.
+    pub fn read_frame_image(
+        sprite_dir: &Path,
+        frame: FrameId,
+    ) -> Result<RgbaImage, SpriteImageError> {
+        let image = image::open(sprite_dir.join(Self::frame_image_path(frame)))?;
+
+        Ok(image.into_rgba8())
+    }
+
+    /// Reads the image of a single layer within a frame from the Sprite's
+    /// directory.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the image cannot be read or decoded.
+    pub fn read_layer_image(
+        sprite_dir: &Path,
+        frame: FrameId,
+        layer: LayerId,
+    ) -> Result<RgbaImage, SpriteImageError> {
+        let image = image::open(sprite_dir.join(Self::layer_image_path(frame, layer)))?;
+
+        Ok(image.into_rgba8())
+    }
+
+    /// Writes the composite image of a frame into the Sprite's directory. If
+    /// the Sprite has exactly one layer, the same image is written as that
+    /// layer's image as well, which is what the IDE does for imported images.
+    /// Layer images of multi-layer Sprites are left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the Sprite is not a bitmap Sprite, if the image is not the
+    /// size of the Sprite, or if it cannot be written.
+    pub fn write_frame_image(
+        &self,
+        sprite_dir: &Path,
+        frame: FrameId,
+        image: &RgbaImage,
+    ) -> Result<(), SpriteImageError> {
+        self.check_bitmap()?;
+        self.check_image_size(image)?;
+
+        image.save(sprite_dir.join(Self::frame_image_path(frame)))?;
+
+        if let [layer] = self.layers.as_slice() {
+            self.write_layer_image(sprite_dir, frame, layer.common_data.name, image)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the image of a single layer within a frame into the Sprite's
+    /// directory, creating the layer folders if needed.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the Sprite is not a bitmap Sprite, if the image is not the
+    /// size of the Sprite, or if it cannot be written.
+    pub fn write_layer_image(
+        &self,
+        sprite_dir: &Path,
+        frame: FrameId,
+        layer: LayerId,
+        image: &RgbaImage,
+    ) -> Result<(), SpriteImageError> {
+        self.check_bitmap()?;
+        self.check_image_size(image)?;
+
+        let path = sprite_dir.join(Self::layer_image_path(frame, layer));
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        image.save(path)?;
+
+        Ok(())
+    }
+
+    /// Errors if the Sprite is not a [`SpriteType::Bitmap`], since only
+    /// bitmap Sprites have frame and layer images.
+    pub(crate) fn check_bitmap(&self) -> Result<(), SpriteImageError> {
+        match self.resource_sprite_type {
+            SpriteType::Bitmap => Ok(()),
+            other => Err(SpriteImageError::NotBitmap(other)),
+        }
+    }
+
+    pub(crate) fn check_image_size(&self, image: &RgbaImage) -> Result<(), SpriteImageError> {
+        if image.width() as usize != self.width.get()
+            || image.height() as usize != self.height.get()
+        {
+            return Err(SpriteImageError::WrongSize(
+                image.width(),
+                image.height(),
+                self.width.get(),
+                self.height.get(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,415 @@
+use super::{GmEncodedColor, NineSlice, Rect, Sprite, TileMode};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
+
+/// The tile mode of each part of a nine-slice. The corners are never tiled,
+/// so they have no mode.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone, Copy)]
+#[serde(from = "[TileMode; 5]", into = "[TileMode; 5]")]
+pub struct NineSliceTileModes {
+    pub left: TileMode,
+    pub top: TileMode,
+    pub right: TileMode,
+    pub bottom: TileMode,
+    pub center: TileMode,
+}
+
+impl From<[TileMode; 5]> for NineSliceTileModes {
+    fn from([left, top, right, bottom, center]: [TileMode; 5]) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+            center,
+        }
+    }
+}
+
+impl From<NineSliceTileModes> for [TileMode; 5] {
+    fn from(o: NineSliceTileModes) -> Self {
+        [o.left, o.top, o.right, o.bottom, o.center]
+    }
+}
+
+/// The colour of each of a nine-slice's guides in the IDE.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone, Copy)]
+#[serde(from = "[GmEncodedColor; 4]", into = "[GmEncodedColor; 4]")]
+pub struct NineSliceGuideColours {
+    pub left: GmEncodedColor,
+    pub top: GmEncodedColor,
+    pub right: GmEncodedColor,
+    pub bottom: GmEncodedColor,
+}
+
+impl From<[GmEncodedColor; 4]> for NineSliceGuideColours {
+    fn from([left, top, right, bottom]: [GmEncodedColor; 4]) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+}
+
+impl From<NineSliceGuideColours> for [GmEncodedColor; 4] {
+    fn from(o: NineSliceGuideColours) -> Self {
+        [o.left, o.top, o.right, o.bottom]
+    }
+}
+
+impl GmEncodedColor {
+    /// Creates a colour out of its raw, encoded value.
+    pub const fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    /// Creates an opaque colour out of its red, green and blue components.
+    pub const fn from_rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self::from_rgba(red, green, blue, u8::MAX)
+    }
+
+    /// Creates a colour out of its red, green, blue and alpha components.
+    pub const fn from_rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Self(red as u64 | (green as u64) << 8 | (blue as u64) << 16 | (alpha as u64) << 24)
+    }
+
+    /// The raw, encoded value of the colour.
+    pub const fn inner(self) -> u64 {
+        self.0
+    }
+
+    /// The red, green and blue components of the colour.
+    pub const fn rgb(self) -> [u8; 3] {
+        [self.0 as u8, (self.0 >> 8) as u8, (self.0 >> 16) as u8]
+    }
+
+    /// The alpha component of the colour.
+    pub const fn alpha(self) -> u8 {
+        (self.0 >> 24) as u8
+    }
+}
+
+/// Nine-slice bounds which do not fit within their Sprite.
+#[derive(Debug, thiserror::Error, PartialEq, Eq, Clone, Copy)]
+pub enum NineSliceError {
+    #[error("left ({left}) and right ({right}) bounds are wider than the sprite ({width})")]
+    TooWide { left: u64, right: u64, width: usize },
+    #[error("top ({top}) and bottom ({bottom}) bounds are taller than the sprite ({height})")]
+    TooTall {
+        top: u64,
+        bottom: u64,
+        height: usize,
+    },
+}
+
+/// One of the nine parts of a nine-slice, as drawn at a given size.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct NineSlicePatch {
+    /// The pixels of the Sprite which make up this patch.
+    pub source: Rect,
+
+    /// Where the patch is drawn, relative to the top left of the drawn
+    /// Sprite.
+    pub destination: Rect,
+
+    /// How the source fills the destination, or `None` for the corners,
+    /// which are always drawn at their source size (unless the target is too
+    /// small to fit them, in which case they are squashed).
+    pub tile_mode: Option<TileMode>,
+}
+
+/// A `(start, length)` pair along one axis.
+type Span = (u32, u32);
+
+impl NineSlice {
+    /// Checks that the bounds fit within a Sprite of the given size. The left
+    /// and right bounds are insets from their edge of the Sprite, as are the
+    /// top and bottom bounds, so they may not overlap.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the left and right, or top and bottom, bounds add up to more
+    /// than the Sprite's width or height.
+    pub fn validate(
+        &self,
+        width: NonZeroUsize,
+        height: NonZeroUsize,
+    ) -> Result<(), NineSliceError> {
+        if self.left.saturating_add(self.right) > width.get() as u64 {
+            return Err(NineSliceError::TooWide {
+                left: self.left,
+                right: self.right,
+                width: width.get(),
+            });
+        }
+        if self.top.saturating_add(self.bottom) > height.get() as u64 {
+            return Err(NineSliceError::TooTall {
+                top: self.top,
+                bottom: self.bottom,
+                height: height.get(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Computes the nine patches to draw a Sprite of `width` by `height` at
+    /// `target_width` by `target_height`, in reading order: top left, top,
+    /// top right, left, center, right, bottom left, bottom, bottom right.
+    ///
+    /// Patches may be empty.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the bounds do not fit within the Sprite, as with
+    /// [`validate`](Self::validate), or if the Sprite or its bounds are too
+    /// large to measure in pixels with a `u32`.
+    pub fn patches(
+        &self,
+        width: NonZeroUsize,
+        height: NonZeroUsize,
+        target_width: u32,
+        target_height: u32,
+    ) -> Result<[NineSlicePatch; 9], NineSliceError> {
+        self.validate(width, height)?;
+
+        let columns = Self::spans(self.left, self.right, width, target_width).ok_or(
+            NineSliceError::TooWide {
+                left: self.left,
+                right: self.right,
+                width: width.get(),
+            },
+        )?;
+        let rows = Self::spans(self.top, self.bottom, height, target_height).ok_or(
+            NineSliceError::TooTall {
+                top: self.top,
+                bottom: self.bottom,
+                height: height.get(),
+            },
+        )?;
+        let modes = [
+            [None, Some(self.tile_mode.top), None],
+            [
+                Some(self.tile_mode.left),
+                Some(self.tile_mode.center),
+                Some(self.tile_mode.right),
+            ],
+            [None, Some(self.tile_mode.bottom), None],
+        ];
+
+        Ok(std::array::from_fn(|i| {
+            let ((src_x, src_w), (dst_x, dst_w)) = columns[i % 3];
+            let ((src_y, src_h), (dst_y, dst_h)) = rows[i / 3];
+
+            NineSlicePatch {
+                source: Rect {
+                    x: src_x,
+                    y: src_y,
+                    w: src_w,
+                    h: src_h,
+                },
+                destination: Rect {
+                    x: dst_x,
+                    y: dst_y,
+                    w: dst_w,
+                    h: dst_h,
+                },
+                tile_mode: modes[i / 3][i % 3],
+            }
+        }))
+    }
+
+    /// Splits a source and target length into their three spans along one
+    /// axis, as `(start, length)` pairs. If the edges do not fit within the
+    /// target, they are shrunk in proportion and the center is dropped.
+    /// Returns `None` if a length does not fit in a `u32`.
+    fn spans(
+        leading: u64,
+        trailing: u64,
+        size: NonZeroUsize,
+        target: u32,
+    ) -> Option<[(Span, Span); 3]> {
+        let size = u32::try_from(size.get()).ok()?;
+        let leading = u32::try_from(leading).ok()?.min(size);
+        let trailing = u32::try_from(trailing).ok()?.min(size - leading);
+        let edges = leading + trailing;
+
+        let (dst_leading, dst_trailing) = if edges > target {
+            let dst_leading = (u64::from(leading) * u64::from(target) / u64::from(edges)) as u32;
+            (dst_leading, target - dst_leading)
+        } else {
+            (leading, trailing)
+        };
+        let dst_center = target - dst_leading - dst_trailing;
+
+        Some([
+            ((0, leading), (0, dst_leading)),
+            ((leading, size - edges), (dst_leading, dst_center)),
+            (
+                (size - trailing, trailing),
+                (target - dst_trailing, dst_trailing),
+            ),
+        ])
+    }
+}
+
+impl Sprite {
+    /// Checks the Sprite's nine-slice bounds, if it has any, against its
+    /// size. Disabled nine-slices are checked as well, since the IDE keeps
+    /// their bounds around.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the bounds do not fit within the Sprite; see
+    /// [`NineSlice::validate`].
+    pub fn validate_nine_slice(&self) -> Result<(), NineSliceError> {
+        match &self.nine_slice {
+            Some(nine_slice) => nine_slice.validate(self.width, self.height),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn size(value: usize) -> NonZeroUsize {
+        NonZeroUsize::new(value).unwrap()
+    }
+
+    #[test]
+    fn serialization_is_unchanged() {
+        let json = r#"{
+            "bottom": 4,
+            "enabled": true,
+            "guideColour": [4294902015, 4294902015, 4294902015, 4294902015],
+            "highlightColour": 1728023040,
+            "highlightStyle": 0,
+            "left": 2,
+            "resourceType": "GMNineSliceData",
+            "resourceVersion": "1.0",
+            "right": 2,
+            "tileMode": [0, 1, 0, 2, 4],
+            "top": 4
+        }"#;
+        let nine_slice: NineSlice = serde_json::from_str(json).unwrap();
+        assert_eq!(nine_slice.tile_mode.top, TileMode::Repeat);
+        assert_eq!(nine_slice.tile_mode.bottom, TileMode::Mirror);
+        assert_eq!(nine_slice.tile_mode.center, TileMode::Hide);
+        assert_eq!(nine_slice.guide_colour.right.rgb(), [255, 0, 255]);
+        assert_eq!(nine_slice.guide_colour.right.alpha(), 255);
+        assert_eq!(nine_slice.highlight_colour.rgb(), [0, 138, 255]);
+
+        let value = serde_json::to_value(&nine_slice).unwrap();
+        assert_eq!(value["tileMode"], serde_json::json!([0, 1, 0, 2, 4]));
+        assert_eq!(value["guideColour"][0], 4294902015u64);
+
+        assert_eq!(GmEncodedColor::from_rgb(255, 0, 255).inner(), 4294902015);
+    }
+
+    #[test]
+    fn validate_bounds() {
+        let nine_slice = NineSlice {
+            left: 4,
+            right: 4,
+            top: 6,
+            bottom: 3,
+            ..Default::default()
+        };
+        assert_eq!(nine_slice.validate(size(8), size(9)), Ok(()));
+        assert_eq!(
+            nine_slice.validate(size(7), size(9)),
+            Err(NineSliceError::TooWide {
+                left: 4,
+                right: 4,
+                width: 7
+            })
+        );
+        assert!(matches!(
+            nine_slice.validate(size(8), size(8)),
+            Err(NineSliceError::TooTall { .. })
+        ));
+    }
+
+    #[test]
+    fn patches() {
+        let nine_slice = NineSlice {
+            left: 2,
+            right: 3,
+            top: 1,
+            bottom: 1,
+            ..Default::default()
+        };
+
+        let patches = nine_slice.patches(size(8), size(4), 20, 10).unwrap();
+        assert_eq!(
+            patches[0].destination,
+            Rect {
+                x: 0,
+                y: 0,
+                w: 2,
+                h: 1
+            }
+        );
+        assert_eq!(patches[0].tile_mode, None);
+        assert_eq!(
+            patches[4].source,
+            Rect {
+                x: 2,
+                y: 1,
+                w: 3,
+                h: 2
+            }
+        );
+        assert_eq!(
+            patches[4].destination,
+            Rect {
+                x: 2,
+                y: 1,
+                w: 15,
+                h: 8
+            }
+        );
+        assert_eq!(patches[4].tile_mode, Some(TileMode::Stretch));
+        assert_eq!(
+            patches[8].destination,
+            Rect {
+                x: 17,
+                y: 9,
+                w: 3,
+                h: 1
+            }
+        );
+
+        let patches = nine_slice.patches(size(8), size(4), 4, 4).unwrap();
+        assert_eq!(patches[0].destination.w, 1);
+        assert_eq!(patches[1].destination.w, 0);
+        assert_eq!(
+            patches[2].destination,
+            Rect {
+                x: 1,
+                y: 0,
+                w: 3,
+                h: 1
+            }
+        );
+
+        // bounds past `u32::MAX` used to wrap around to small insets.
+        let huge = NineSlice {
+            left: 1 << 32,
+            ..nine_slice
+        };
+        assert_eq!(
+            huge.patches(size(8), size(4), 4, 4),
+            Err(NineSliceError::TooWide {
+                left: 1 << 32,
+                right: nine_slice.right,
+                width: 8
+            })
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use super::{Origin, Sprite};
+use std::num::NonZeroUsize;
+
+impl Origin {
+    /// The pixel coordinates which the IDE assigns to this preset for a Sprite
+    /// of the given size, or `None` for [`Origin::Custom`].
+    ///
+    /// Centers are rounded down, and the right and bottom edges are the last
+    /// pixel of the Sprite, so a 32x32 Sprite has a `MiddleCenter` of
+    /// `(16, 16)` and a `BottomRight` of `(31, 31)`.
+    pub fn point(self, width: NonZeroUsize, height: NonZeroUsize) -> Option<(i32, i32)> {
+        let left = 0;
+        let center = (width.get() / 2) as i32;
+        let right = width.get() as i32 - 1;
+        let top = 0;
+        let middle = (height.get() / 2) as i32;
+        let bottom = height.get() as i32 - 1;
+
+        let output = match self {
+            Origin::TopLeft => (left, top),
+            Origin::TopCenter => (center, top),
+            Origin::TopRight => (right, top),
+            Origin::MiddleLeft => (left, middle),
+            Origin::MiddleCenter => (center, middle),
+            Origin::MiddleRight => (right, middle),
+            Origin::BottomLeft => (left, bottom),
+            Origin::BottomCenter => (center, bottom),
+            Origin::BottomRight => (right, bottom),
+            Origin::Custom => return None,
+        };
+
+        Some(output)
+    }
+}
+
+/// A Sprite whose `origin` is a preset, but whose `sequence.xorigin` and
+/// `sequence.yorigin` are not where that preset would put them.
+#[derive(Debug, thiserror::Error, PartialEq, Eq, Clone, Copy)]
+#[error("origin {origin:?} should be at {expected:?}, but is at {found:?}")]
+pub struct OriginMismatch {
+    pub origin: Origin,
+    pub expected: (i32, i32),
+    pub found: (i32, i32),
+}
+
+impl Sprite {
+    /// The pixel coordinates of the Sprite's origin, as stored in its
+    /// sequence. This is what the runtime uses, regardless of `origin`.
+    pub fn origin_point(&self) -> (i32, i32) {
+        (self.sequence.xorigin, self.sequence.yorigin)
+    }
+
+    /// Sets the Sprite's origin, moving `sequence.xorigin` and
+    /// `sequence.yorigin` to match the preset. Setting [`Origin::Custom`]
+    /// keeps the current coordinates; use
+    /// [`set_custom_origin`](Self::set_custom_origin) to move them.
+    pub fn set_origin(&mut self, origin: Origin) {
+        self.origin = origin;
+        self.update_origin();
+    }
+
+    /// Sets the Sprite's origin to [`Origin::Custom`] at the given pixel.
+    /// Coordinates outside of the Sprite are allowed, as in the IDE.
+    pub fn set_custom_origin(&mut self, x: i32, y: i32) {
+        self.origin = Origin::Custom;
+        self.sequence.xorigin = x;
+        self.sequence.yorigin = y;
+    }
+
+    /// Recomputes the origin's coordinates from its preset and the Sprite's
+    /// current size. This should be called after changing `width` or
+    /// `height`. Custom origins are left where they are.
+    pub fn update_origin(&mut self) {
+        if let Some((x, y)) = self.origin.point(self.width, self.height) {
+            self.sequence.xorigin = x;
+            self.sequence.yorigin = y;
+        }
+    }
+
+    /// Checks that a preset origin's coordinates match the Sprite's size.
+    /// Custom origins are always valid.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the origin is a preset, and `sequence.xorigin` or
+    /// `sequence.yorigin` are not where that preset would put them for the
+    /// Sprite's `width` and `height`.
+    pub fn validate_origin(&self) -> Result<(), OriginMismatch> {
+        match self.origin.point(self.width, self.height) {
+            Some(expected) if expected != self.origin_point() => Err(OriginMismatch {
+                origin: self.origin,
+                expected,
+                found: self.origin_point(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn size(value: usize) -> NonZeroUsize {
+        NonZeroUsize::new(value).unwrap()
+    }
+
+    #[test]
+    fn preset_points() {
+        assert_eq!(Origin::TopLeft.point(size(32), size(32)), Some((0, 0)));
+        assert_eq!(
+            Origin::MiddleCenter.point(size(32), size(32)),
+            Some((16, 16))
+        );
+        assert_eq!(Origin::MiddleCenter.point(size(33), size(7)), Some((16, 3)));
+        assert_eq!(
+            Origin::BottomRight.point(size(32), size(16)),
+            Some((31, 15))
+        );
+        assert_eq!(Origin::Custom.point(size(32), size(32)), None);
+    }
+
+    #[test]
+    fn origin_stays_consistent() {
+        let mut sprite = Sprite::new("spr_test", size(32), size(32));
+        sprite.set_origin(Origin::BottomCenter);
+        assert_eq!(sprite.origin_point(), (16, 31));
+        assert_eq!(sprite.validate_origin(), Ok(()));
+
+        sprite.width = size(64);
+        assert_eq!(
+            sprite.validate_origin(),
+            Err(OriginMismatch {
+                origin: Origin::BottomCenter,
+                expected: (32, 31),
+                found: (16, 31),
+            })
+        );
+        sprite.update_origin();
+        assert_eq!(sprite.origin_point(), (32, 31));
+
+        sprite.set_custom_origin(-4, 100);
+        sprite.width = size(8);
+        sprite.update_origin();
+        assert_eq!(sprite.origin_point(), (-4, 100));
+        assert_eq!(sprite.validate_origin(), Ok(()));
+    }
+}
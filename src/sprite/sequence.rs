@@ -1,7 +1,8 @@
-use super::{consts, FilesystemPath, ResourceVersion};
+use super::{consts, FilesystemPath, FrameId, ResourceVersion};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use smart_default::SmartDefault;
+use std::collections::BTreeMap;
 
 create_guarded_uuid!(SpriteSequenceId);
 
@@ -26,17 +27,15 @@ pub struct SpriteSequence {
     pub backdrop_x_offset: f32,
     pub backdrop_y_offset: f32,
 
-    /// The sprite events, which are always the Default value of SpriteEvents,
-    /// for the Sprite.
+    /// The broadcast messages of the Sprite.
     pub events: SpriteEvents,
 
     #[default(None)]
     pub event_stub_script: Option<()>,
 
-    /// This can be a `{}`, which basically is
-    /// impossible for us to parse. So that's a mess.
-    #[default(serde_json::Value::Object(serde_json::Map::new()))]
-    pub event_to_function: serde_json::Value,
+    /// Maps broadcast messages to the functions which handle them. The IDE
+    /// only fills this in for Sequences, so it is empty for Sprites.
+    pub event_to_function: BTreeMap<String, String>,
 
     /// The number of frames of the Sprite. GMS2 records this as an f32 due to
     /// its shared status between sequences -- this can be converted to a
@@ -47,13 +46,13 @@ pub struct SpriteSequence {
     /// Editor. It has no effect otherwise.
     pub lock_origin: bool,
 
-    /// The sprite moments, which are always the Default value of SpriteMoments,
-    /// for the Sprite.
+    /// The sprite moments, which are almost always the Default value of
+    /// SpriteMoments, for the Sprite.
     pub moments: SpriteMoments,
 
-    /// Will always be One.
-    #[default = 1]
-    pub playback: usize,
+    /// How the sequence plays once it reaches its end. This is always
+    /// [`SequencePlayback::Loop`] for Sprites made in the IDE.
+    pub playback: SequencePlayback,
 
     /// The playback speed of the Sequence in terms of the PlaybackSpeed type.
     pub playback_speed: f32,
@@ -93,9 +92,9 @@ pub struct VisibleRange {
     pub y: f32,
 }
 
-/// These are the "events" which a Sprite is subscribed to. It will always be
-/// its default value. It exists due to sharing resources with Sequences.
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, SmartDefault)]
+/// These are the broadcast messages which a Sprite sends as it animates. It
+/// exists due to sharing resources with Sequences.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, SmartDefault)]
 #[serde(rename_all = "camelCase")]
 pub struct SpriteEvents {
     /// The name of the Resource Type. This is a C# generic, so this Serde
@@ -105,16 +104,16 @@ pub struct SpriteEvents {
     /// The resource version of the SpriteEvent.
     pub resource_version: ResourceVersion,
 
-    /// The keyframes which a SpriteEvent is assigned to. Because Sprites do not
-    /// have access to all the resources of a sequence, they are always
-    /// subscribed to 0 keyframes.
+    /// The broadcast message keyframes of the Sprite. Each keyframe sends its
+    /// messages when the animation reaches its `key`.
     #[serde(rename = "Keyframes")]
-    pub keyframes: Vec<()>,
+    pub keyframes: Vec<MessageKeyframe>,
 }
 
-/// These are the "moments" which a Sprite is subscribed to. It will always be
-/// its default value. This is due to sharing resources with Sequences.
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, SmartDefault)]
+/// These are the "moments" which a Sprite is subscribed to. It will almost
+/// always be its default value. This is due to sharing resources with
+/// Sequences.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, SmartDefault)]
 #[serde(rename_all = "camelCase")]
 pub struct SpriteMoments {
     /// The name of the Resource Type. This is a C# generic, so this Serde
@@ -124,11 +123,108 @@ pub struct SpriteMoments {
     /// The resource version of the SpriteMoment. Currently, it is always "1.0".
     pub resource_version: ResourceVersion,
 
-    /// The keyframes which a SpriteEvent is assigned to. Because Sprites do not
-    /// have access to all the resources of a sequence, they are always
-    /// subscribed to 0 keyframes.
+    /// The moment keyframes of the Sprite. Sprites cannot call functions
+    /// from their moments, so this is generally empty.
     #[serde(rename = "Keyframes")]
-    pub keyframes: Vec<()>,
+    pub keyframes: Vec<MomentKeyframe>,
+}
+
+/// A keyframe which sends broadcast messages when the animation reaches it.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, SmartDefault)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageKeyframe {
+    pub resource_type: consts::SpriteMessageKeyframe,
+
+    pub resource_version: ResourceVersion,
+
+    #[serde(rename = "Channels")]
+    pub channels: MessageChannels,
+
+    #[serde(rename = "Disabled")]
+    pub disabled: bool,
+
+    /// A SpriteSequenceId, apparently with no relation to any other ID.
+    pub id: SpriteSequenceId,
+
+    #[serde(rename = "IsCreationKey")]
+    pub is_creation_key: bool,
+
+    #[serde(rename = "Key")]
+    pub key: f64,
+
+    #[serde(rename = "Length")]
+    #[default = 1.0]
+    pub length: f64,
+
+    #[serde(rename = "Stretch")]
+    pub stretch: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct MessageChannels {
+    #[serde(rename = "0")]
+    pub zero: MessageEventKeyframe,
+}
+
+/// The broadcast messages sent by a [`MessageKeyframe`].
+#[derive(Debug, Serialize, Deserialize, SmartDefault, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageEventKeyframe {
+    pub resource_type: consts::MessageEventKeyframe,
+    pub resource_version: ResourceVersion,
+
+    /// The messages, in the order they are sent.
+    #[serde(rename = "Events")]
+    pub events: Vec<String>,
+}
+
+/// A keyframe which calls functions when the animation reaches it.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, SmartDefault)]
+#[serde(rename_all = "camelCase")]
+pub struct MomentKeyframe {
+    pub resource_type: consts::SpriteMomentKeyframe,
+
+    pub resource_version: ResourceVersion,
+
+    #[serde(rename = "Channels")]
+    pub channels: MomentChannels,
+
+    #[serde(rename = "Disabled")]
+    pub disabled: bool,
+
+    /// A SpriteSequenceId, apparently with no relation to any other ID.
+    pub id: SpriteSequenceId,
+
+    #[serde(rename = "IsCreationKey")]
+    pub is_creation_key: bool,
+
+    #[serde(rename = "Key")]
+    pub key: f64,
+
+    #[serde(rename = "Length")]
+    #[default = 1.0]
+    pub length: f64,
+
+    #[serde(rename = "Stretch")]
+    pub stretch: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct MomentChannels {
+    #[serde(rename = "0")]
+    pub zero: MomentsEventKeyframe,
+}
+
+/// The functions called by a [`MomentKeyframe`].
+#[derive(Debug, Serialize, Deserialize, SmartDefault, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MomentsEventKeyframe {
+    pub resource_type: consts::MomentsEventKeyframe,
+    pub resource_version: ResourceVersion,
+
+    /// The names of the functions, in the order they are called.
+    #[serde(rename = "Events")]
+    pub events: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, SmartDefault)]
@@ -240,3 +336,124 @@ pub enum PlaybackSpeed {
     FramesPerSecond,
     FramesPerGameFrame,
 }
+
+/// What a sequence does once it reaches its end.
+#[derive(Serialize_repr, Deserialize_repr, PartialEq, Debug, SmartDefault, Copy, Clone, Eq)]
+#[repr(u8)]
+pub enum SequencePlayback {
+    /// The sequence stops, holding its last frame.
+    Once,
+    /// The sequence starts over from the beginning.
+    #[default]
+    Loop,
+    /// The sequence plays backwards to the beginning, and then forwards
+    /// again.
+    PingPong,
+}
+
+impl SpriteSequence {
+    /// The number of sequence units which play each second. For Sprites, a
+    /// unit is a single frame, so this is the frames per second.
+    ///
+    /// `game_fps` is only used if the `playback_speed_type` is
+    /// [`PlaybackSpeed::FramesPerGameFrame`].
+    pub fn units_per_second(&self, game_fps: f64) -> f64 {
+        match self.playback_speed_type {
+            PlaybackSpeed::FramesPerSecond => self.playback_speed.into(),
+            PlaybackSpeed::FramesPerGameFrame => f64::from(self.playback_speed) * game_fps,
+        }
+    }
+
+    /// Returns the frame which is shown `time` seconds after the sequence
+    /// starts playing, in a game running at `game_fps`.
+    ///
+    /// The frame is the enabled keyframe on the frames track whose `key` and
+    /// `length` cover that moment. A frame keyframe shows a single image, so
+    /// `stretch` does not change which frame is shown. Once the end of the
+    /// sequence is reached, its `playback` decides what happens.
+    ///
+    /// Returns `None` if the sequence is empty or does not play, or if no
+    /// enabled keyframe covers the moment.
+    pub fn frame_at(&self, time: f64, game_fps: f64) -> Option<FrameId> {
+        let length = self.length;
+        let position = time * self.units_per_second(game_fps);
+        if length <= 0.0 || !position.is_finite() {
+            return None;
+        }
+
+        let position = match self.playback {
+            SequencePlayback::Once => position.clamp(0.0, length),
+            SequencePlayback::Loop => position.rem_euclid(length),
+            SequencePlayback::PingPong => {
+                let position = position.rem_euclid(length * 2.0);
+                if position > length {
+                    length * 2.0 - position
+                } else {
+                    position
+                }
+            }
+        };
+
+        let keyframes = &self.tracks.first()?.keyframes.keyframes;
+        let mut enabled = keyframes.iter().filter(|k| !k.disabled);
+
+        let keyframe = if position >= length {
+            // the very end belongs to the frame which ends there.
+            enabled.find(|k| k.key < length && k.key + k.length >= length)
+        } else {
+            enabled.find(|k| k.key <= position && position < k.key + k.length)
+        };
+
+        keyframe.and_then(|k| k.frame_id())
+    }
+}
+
+impl SpriteKeyframe {
+    /// The Id of the frame which this keyframe shows, or `None` if the
+    /// keyframe's channel does not name a frame.
+    pub fn frame_id(&self) -> Option<FrameId> {
+        uuid::Uuid::parse_str(&self.channels.zero.id.name)
+            .ok()
+            .map(FrameId::with_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Sprite, sprite::SequencePlayback};
+    use pretty_assertions::assert_eq;
+    use std::num::NonZeroUsize;
+
+    #[test]
+    fn frame_at() {
+        let one = NonZeroUsize::new(1).unwrap();
+        let mut sprite = Sprite::new("spr_test", one, one);
+        sprite.sequence.playback_speed = 10.0;
+        let a = sprite.push_frame(1.0);
+        let b = sprite.push_frame(2.0);
+        let c = sprite.push_frame(1.0);
+        let sequence = &mut sprite.sequence;
+
+        assert_eq!(sequence.frame_at(0.0, 60.0), Some(a));
+        assert_eq!(sequence.frame_at(0.25, 60.0), Some(b));
+        assert_eq!(sequence.frame_at(0.35, 60.0), Some(c));
+        assert_eq!(sequence.frame_at(0.45, 60.0), Some(a));
+        assert_eq!(sequence.frame_at(-0.05, 60.0), Some(c));
+
+        sequence.playback = SequencePlayback::Once;
+        assert_eq!(sequence.frame_at(100.0, 60.0), Some(c));
+
+        sequence.playback = SequencePlayback::PingPong;
+        assert_eq!(sequence.frame_at(0.45, 60.0), Some(c));
+        assert_eq!(sequence.frame_at(0.55, 60.0), Some(b));
+        assert_eq!(sequence.frame_at(0.85, 60.0), Some(a));
+
+        sequence.playback = SequencePlayback::Loop;
+        sequence.playback_speed_type = super::PlaybackSpeed::FramesPerGameFrame;
+        sequence.playback_speed = 0.5;
+        assert_eq!(sequence.frame_at(1.0 / 30.0, 60.0), Some(b));
+
+        sequence.tracks[0].keyframes.keyframes[1].disabled = true;
+        assert_eq!(sequence.frame_at(1.0 / 30.0, 60.0), None);
+    }
+}
@@ -0,0 +1,409 @@
+use super::{PlaybackSpeed, Sprite, SpriteImageError};
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use std::{fmt, num::NonZeroUsize, path::Path};
+
+/// A rectangle of pixels, as used by spritesheet exporters. `x` and `y` are the
+/// top left corner.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub w: u32,
+    pub h: u32,
+}
+
+impl Rect {
+    /// The exclusive right edge of the rectangle.
+    pub fn right(&self) -> u32 {
+        self.x + self.w
+    }
+
+    /// The exclusive bottom edge of the rectangle.
+    pub fn bottom(&self) -> u32 {
+        self.y + self.h
+    }
+}
+
+/// The size of an image, as used by spritesheet exporters.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct SheetSize {
+    pub w: u32,
+    pub h: u32,
+}
+
+/// A pivot point, normalized against the size of the untrimmed frame, so that
+/// `(0.5, 0.5)` is the center of the frame.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Clone, Copy)]
+pub struct Pivot {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A single frame within a spritesheet's JSON sidecar. This is the layout
+/// shared by Aseprite and TexturePacker's JSON exports.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SheetFrame {
+    /// The name of the frame. In the "hash" layout, this is the key of the
+    /// frame rather than a field.
+    #[serde(default)]
+    pub filename: String,
+
+    /// Where the frame's pixels are on the sheet. If the frame is `rotated`,
+    /// `w` and `h` are the size *before* rotation, so the area on the sheet is
+    /// `h` wide and `w` tall.
+    pub frame: Rect,
+
+    /// Whether the frame was rotated 90 degrees clockwise when packed.
+    #[serde(default)]
+    pub rotated: bool,
+
+    /// Whether transparent pixels were trimmed off the frame. If so,
+    /// `sprite_source_size` and `source_size` describe how to restore them.
+    #[serde(default)]
+    pub trimmed: bool,
+
+    /// Where the trimmed pixels sit within the original, untrimmed frame.
+    pub sprite_source_size: Option<Rect>,
+
+    /// The size of the original, untrimmed frame.
+    pub source_size: Option<SheetSize>,
+
+    /// How long the frame lasts in milliseconds. Only Aseprite writes this.
+    pub duration: Option<u32>,
+
+    /// The pivot of the frame. Only TexturePacker writes this.
+    pub pivot: Option<Pivot>,
+}
+
+impl SheetFrame {
+    /// Crops this frame out of the `sheet`, undoing any rotation and restoring
+    /// any trimmed pixels so that the output is the size of the original
+    /// frame.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the frame is empty or does not fit within the `sheet`.
+    pub fn extract(&self, sheet: &RgbaImage) -> Result<RgbaImage, SpriteImportError> {
+        let Rect { x, y, w, h } = self.frame;
+        let (sheet_w, sheet_h) = if self.rotated { (h, w) } else { (w, h) };
+        let fits = w != 0
+            && h != 0
+            && x.checked_add(sheet_w).is_some_and(|r| r <= sheet.width())
+            && y.checked_add(sheet_h).is_some_and(|b| b <= sheet.height());
+        if !fits {
+            return Err(SpriteImportError::FrameOutOfBounds(self.filename.clone()));
+        }
+
+        let cropped = image::imageops::crop_imm(sheet, x, y, sheet_w, sheet_h).to_image();
+        let cropped = if self.rotated {
+            image::imageops::rotate270(&cropped)
+        } else {
+            cropped
+        };
+
+        let source_size = self.source_size.unwrap_or(SheetSize { w, h });
+        let offset = self.sprite_source_size.unwrap_or_default();
+        if source_size == (SheetSize { w, h }) && offset.x == 0 && offset.y == 0 {
+            return Ok(cropped);
+        }
+
+        let mut canvas = RgbaImage::new(source_size.w, source_size.h);
+        image::imageops::replace(&mut canvas, &cropped, offset.x.into(), offset.y.into());
+
+        Ok(canvas)
+    }
+}
+
+/// The frames of a spritesheet, in sheet order. Exporters write these either
+/// as an array or as a "hash" keyed by the frame's filename -- both layouts
+/// are accepted, and always serialized back as an array.
+#[derive(Debug, Serialize, Default, PartialEq, Clone)]
+#[serde(transparent)]
+pub struct SheetFrames(pub Vec<SheetFrame>);
+
+impl std::ops::Deref for SheetFrames {
+    type Target = Vec<SheetFrame>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'de> Deserialize<'de> for SheetFrames {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::{MapAccess, SeqAccess, Visitor};
+
+        struct FramesVisitor;
+
+        impl<'de> Visitor<'de> for FramesVisitor {
+            type Value = SheetFrames;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an array or map of frames")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut frames = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(frame) = seq.next_element()? {
+                    frames.push(frame);
+                }
+
+                Ok(SheetFrames(frames))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut frames = Vec::with_capacity(map.size_hint().unwrap_or_default());
+                while let Some((filename, mut frame)) = map.next_entry::<String, SheetFrame>()? {
+                    frame.filename = filename;
+                    frames.push(frame);
+                }
+
+                Ok(SheetFrames(frames))
+            }
+        }
+
+        deserializer.deserialize_any(FramesVisitor)
+    }
+}
+
+/// A Sprite created by an importer, along with the image for each of its
+/// frames, in the same order as `sprite.frames`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedSprite {
+    pub sprite: Sprite,
+    pub images: Vec<RgbaImage>,
+}
+
+impl ImportedSprite {
+    /// The fastest playback speed, in frames per second, which
+    /// [`from_frames`](Self::from_frames) will set to keep every keyframe a
+    /// whole number of frames long.
+    pub const MAX_IMPORT_FPS: u32 = 60;
+
+    /// Creates a Sprite out of a list of equally sized frame images.
+    ///
+    /// `durations_ms` gives how long each frame lasts. Sprites have a single
+    /// playback speed, in frames per second, so the speed is set from the
+    /// greatest common divisor of the durations, and each keyframe's
+    /// `length` is stretched to cover its duration. If that would play faster
+    /// than [`MAX_IMPORT_FPS`](Self::MAX_IMPORT_FPS), such as for durations of
+    /// 100 and 101 milliseconds, the speed is set from the shortest duration
+    /// instead, and keyframes are given fractional lengths. If `durations_ms`
+    /// is empty, every frame lasts one unit at the Sprite's default speed.
+    ///
+    /// # Errors
+    ///
+    /// Errors if there are no frames, if the frames differ in size, or if the
+    /// number of durations does not match the number of frames.
+    pub fn from_frames(
+        name: &str,
+        images: Vec<RgbaImage>,
+        durations_ms: &[u32],
+    ) -> Result<Self, SpriteImportError> {
+        let first = images.first().ok_or(SpriteImportError::NoFrames)?;
+        let (width, height) = first.dimensions();
+        if images.iter().any(|v| v.dimensions() != (width, height)) {
+            return Err(SpriteImportError::MismatchedFrameSizes);
+        }
+        if !durations_ms.is_empty() && durations_ms.len() != images.len() {
+            return Err(SpriteImportError::MismatchedDurations);
+        }
+
+        let (Some(width), Some(height)) = (
+            NonZeroUsize::new(width as usize),
+            NonZeroUsize::new(height as usize),
+        ) else {
+            return Err(SpriteImportError::NoFrames);
+        };
+
+        let mut sprite = Sprite::new(name, width, height);
+        sprite.sequence.playback_speed_type = PlaybackSpeed::FramesPerSecond;
+
+        let mut unit = durations_ms.iter().copied().fold(0, gcd);
+        if unit != 0 && 1000 / unit > Self::MAX_IMPORT_FPS {
+            unit = durations_ms.iter().copied().min().unwrap_or_default();
+        }
+        if unit == 0 {
+            for _ in &images {
+                sprite.push_frame(1.0);
+            }
+        } else {
+            sprite.sequence.playback_speed = 1000.0 / unit as f32;
+            for duration in durations_ms {
+                sprite.push_frame(*duration as f64 / unit as f64);
+            }
+        }
+
+        Ok(Self { sprite, images })
+    }
+
+    /// Writes each frame's image into the Sprite's directory. The `.yy` file
+    /// itself is not written.
+    ///
+    /// # Errors
+    ///
+    /// Errors if any of the images cannot be written.
+    pub fn write_images(&self, sprite_dir: &Path) -> Result<(), SpriteImageError> {
+        std::fs::create_dir_all(sprite_dir)?;
+
+        for (frame, image) in self.sprite.frames.iter().zip(self.images.iter()) {
+            self.sprite
+                .write_frame_image(sprite_dir, frame.name, image)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Errors which can occur when importing a spritesheet.
+#[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+pub enum SpriteImportError {
+    #[error("frame `{0}` is empty or does not fit within the sheet")]
+    FrameOutOfBounds(String),
+    #[error("tag `{0}` refers to frames which do not exist")]
+    TagOutOfRange(String),
+    #[error("frames within a sprite must all be the same size")]
+    MismatchedFrameSizes,
+    #[error("the number of durations does not match the number of frames")]
+    MismatchedDurations,
+    #[error("a sprite must have at least one frame")]
+    NoFrames,
+    #[error("`{0}` is not a valid resource name")]
+    InvalidName(String),
+    #[error("more than one sprite would be named `{0}`")]
+    DuplicateName(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn hash_and_array_frames() {
+        let array: SheetFrames = serde_json::from_str(
+            r#"[{"filename":"a","frame":{"x":0,"y":0,"w":2,"h":2}},{"filename":"b","frame":{"x":2,"y":0,"w":2,"h":2}}]"#,
+        )
+        .unwrap();
+        let hash: SheetFrames = serde_json::from_str(
+            r#"{"a":{"frame":{"x":0,"y":0,"w":2,"h":2}},"b":{"frame":{"x":2,"y":0,"w":2,"h":2}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(array, hash);
+        assert_eq!(hash[1].filename, "b");
+    }
+
+    #[test]
+    fn untrim() {
+        let mut sheet = RgbaImage::new(4, 4);
+        sheet.put_pixel(1, 1, image::Rgba([255, 0, 0, 255]));
+
+        let frame = SheetFrame {
+            frame: Rect {
+                x: 1,
+                y: 1,
+                w: 1,
+                h: 1,
+            },
+            trimmed: true,
+            sprite_source_size: Some(Rect {
+                x: 2,
+                y: 3,
+                w: 1,
+                h: 1,
+            }),
+            source_size: Some(SheetSize { w: 4, h: 5 }),
+            ..Default::default()
+        };
+
+        let output = frame.extract(&sheet).unwrap();
+        assert_eq!(output.dimensions(), (4, 5));
+        assert_eq!(output.get_pixel(2, 3), &image::Rgba([255, 0, 0, 255]));
+        assert_eq!(output.get_pixel(1, 1), &image::Rgba([0, 0, 0, 0]));
+
+        let rotated = SheetFrame {
+            frame: Rect {
+                x: 0,
+                y: 0,
+                w: 3,
+                h: 2,
+            },
+            rotated: true,
+            ..Default::default()
+        };
+        let mut sheet = RgbaImage::new(2, 3);
+        // the top left pixel of the frame ends up in the top right when
+        // rotated clockwise.
+        sheet.put_pixel(1, 0, image::Rgba([0, 0, 255, 255]));
+        let output = rotated.extract(&sheet).unwrap();
+        assert_eq!(output.dimensions(), (3, 2));
+        assert_eq!(output.get_pixel(0, 0), &image::Rgba([0, 0, 255, 255]));
+
+        let out_of_bounds = SheetFrame {
+            frame: Rect {
+                x: 3,
+                y: 3,
+                w: 2,
+                h: 2,
+            },
+            ..Default::default()
+        };
+        assert!(out_of_bounds.extract(&sheet).is_err());
+    }
+
+    #[test]
+    fn uneven_durations() {
+        let images = vec![RgbaImage::new(2, 2); 3];
+        let imported = ImportedSprite::from_frames("spr_test", images, &[100, 200, 100]).unwrap();
+
+        let keyframes = &imported.sprite.frames_track().unwrap().keyframes.keyframes;
+        assert_eq!(imported.sprite.sequence.playback_speed, 10.0);
+        assert_eq!(imported.sprite.sequence.length, 4.0);
+        assert_eq!(
+            keyframes
+                .iter()
+                .map(|v| (v.key, v.length))
+                .collect::<Vec<_>>(),
+            vec![(0.0, 1.0), (1.0, 2.0), (3.0, 1.0)]
+        );
+        assert_eq!(
+            imported.sprite.sequence.playback_speed_type,
+            PlaybackSpeed::FramesPerSecond
+        );
+        assert_eq!(
+            keyframes[1].channels.zero.id.name,
+            imported.sprite.frames[1].name.inner().to_string()
+        );
+    }
+
+    #[test]
+    fn nearly_even_durations() {
+        let images = vec![RgbaImage::new(2, 2); 3];
+        let imported = ImportedSprite::from_frames("spr_test", images, &[100, 101, 200]).unwrap();
+
+        // a 1 ms unit would be 1000 fps, so the shortest frame is the unit.
+        let keyframes = &imported.sprite.frames_track().unwrap().keyframes.keyframes;
+        assert_eq!(imported.sprite.sequence.playback_speed, 10.0);
+        assert_eq!(
+            keyframes.iter().map(|v| v.length).collect::<Vec<_>>(),
+            vec![1.0, 1.01, 2.0]
+        );
+        assert_eq!(imported.sprite.sequence.length, 4.01);
+    }
+}
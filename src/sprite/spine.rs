@@ -0,0 +1,169 @@
+use super::{Sprite, SpriteType};
+use std::path::{Path, PathBuf};
+
+/// The files which make up a Spine Sprite, relative to the Sprite's
+/// directory.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct SpineFiles {
+    /// The skeleton data, exported from Spine as JSON.
+    pub json: PathBuf,
+
+    /// The texture atlas, which names the images of its pages.
+    pub atlas: PathBuf,
+}
+
+/// Errors which can occur when validating the files of a Spine Sprite.
+#[derive(Debug, thiserror::Error)]
+pub enum SpineError {
+    #[error("sprite is a {0:?} sprite, not a Spine sprite")]
+    NotSpine(SpriteType),
+    #[error("Spine sprite has no frame to name its files after")]
+    NoFrames,
+    #[error("missing Spine file {}", .0.display())]
+    Missing(PathBuf),
+    #[error("failed to read {}: {1}", .0.display())]
+    Io(PathBuf, #[source] std::io::Error),
+    #[error("skeleton data is not valid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("skeleton data has no `skeleton` section")]
+    NotASkeleton,
+    #[error("atlas page {} does not exist", .0.display())]
+    MissingPage(PathBuf),
+}
+
+impl Sprite {
+    /// The paths of the skeleton data and texture atlas of a Spine Sprite,
+    /// relative to its directory. The IDE names both after the Sprite's only
+    /// frame.
+    ///
+    /// Returns `None` if the Sprite is not a Spine Sprite, or has no frames.
+    pub fn spine_files(&self) -> Option<SpineFiles> {
+        if self.resource_sprite_type != SpriteType::Spine {
+            return None;
+        }
+        let frame = self.frames.first()?.name.inner();
+
+        Some(SpineFiles {
+            json: PathBuf::from(format!("{}.json", frame)),
+            atlas: PathBuf::from(format!("{}.atlas", frame)),
+        })
+    }
+
+    /// Checks that the files of a Spine Sprite exist in the Sprite's
+    /// directory, that the skeleton data is a Spine skeleton, and that every
+    /// page image named by the atlas exists. Returns the files' paths,
+    /// relative to the Sprite's directory.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the Sprite is not a Spine Sprite, or if any of its files are
+    /// missing or invalid.
+    pub fn validate_spine(&self, sprite_dir: &Path) -> Result<SpineFiles, SpineError> {
+        if self.resource_sprite_type != SpriteType::Spine {
+            return Err(SpineError::NotSpine(self.resource_sprite_type));
+        }
+        let files = self.spine_files().ok_or(SpineError::NoFrames)?;
+
+        let read = |path: &Path| {
+            let full_path = sprite_dir.join(path);
+            std::fs::read_to_string(&full_path).map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    SpineError::Missing(path.to_owned())
+                } else {
+                    SpineError::Io(full_path, e)
+                }
+            })
+        };
+
+        let json: serde_json::Value = serde_json::from_str(&read(&files.json)?)?;
+        if json.get("skeleton").is_none() {
+            return Err(SpineError::NotASkeleton);
+        }
+
+        let atlas = read(&files.atlas)?;
+        for page in atlas_pages(&atlas) {
+            if !sprite_dir.join(page).is_file() {
+                return Err(SpineError::MissingPage(PathBuf::from(page)));
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+/// The image names of each page of a libGDX texture atlas, as written by
+/// Spine. Every page starts with its image's name, on the first line of a
+/// block of lines, and blocks are separated by blank lines.
+fn atlas_pages(atlas: &str) -> Vec<&str> {
+    let mut output = vec![];
+    let mut new_block = true;
+
+    for line in atlas.lines().map(str::trim) {
+        if line.is_empty() {
+            new_block = true;
+        } else if new_block {
+            output.push(line);
+            new_block = false;
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprite::FrameId;
+    use pretty_assertions::assert_eq;
+
+    const ATLAS: &str = "
+hero.png
+size: 512,256
+format: RGBA8888
+filter: Linear,Linear
+repeat: none
+head
+  rotate: false
+  xy: 2, 2
+
+hero2.png
+\tsize: 64,64
+body
+\tbounds: 0,0,64,64
+";
+
+    #[test]
+    fn pages() {
+        assert_eq!(atlas_pages(ATLAS), vec!["hero.png", "hero2.png"]);
+    }
+
+    #[test]
+    fn validate_spine() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut sprite = Sprite::default();
+        assert!(sprite.spine_files().is_none());
+        assert!(matches!(
+            sprite.validate_spine(dir.path()),
+            Err(SpineError::NotSpine(SpriteType::Bitmap))
+        ));
+
+        sprite.resource_sprite_type = SpriteType::Spine;
+        sprite.frames.push(crate::CommonData::new(FrameId::new()));
+        let files = sprite.spine_files().unwrap();
+        assert!(matches!(
+            sprite.validate_spine(dir.path()),
+            Err(SpineError::Missing(path)) if path == files.json
+        ));
+
+        std::fs::write(dir.path().join(&files.json), r#"{"skeleton": {}}"#).unwrap();
+        std::fs::write(dir.path().join(&files.atlas), ATLAS).unwrap();
+        std::fs::write(dir.path().join("hero.png"), []).unwrap();
+        assert!(matches!(
+            sprite.validate_spine(dir.path()),
+            Err(SpineError::MissingPage(page)) if page == Path::new("hero2.png")
+        ));
+
+        std::fs::write(dir.path().join("hero2.png"), []).unwrap();
+        assert_eq!(sprite.validate_spine(dir.path()).unwrap(), files);
+    }
+}
@@ -0,0 +1,221 @@
+use super::{ImportedSprite, SheetFrame, SheetFrames, SheetSize, SpriteImportError};
+use crate::{ResourceNameValidator, TexturePath};
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+
+/// The JSON data file which TexturePacker (and most generic spritesheet
+/// packers) write next to a sheet, in either the "JSON (Hash)" or
+/// "JSON (Array)" layout.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Clone)]
+pub struct TexturePackerSheet {
+    /// Every frame on the sheet.
+    pub frames: SheetFrames,
+
+    /// Metadata about the sheet.
+    #[serde(default)]
+    pub meta: TexturePackerMeta,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct TexturePackerMeta {
+    /// The filename of the sheet's image, relative to the data file.
+    pub image: Option<String>,
+
+    /// The size of the sheet's image.
+    pub size: Option<SheetSize>,
+}
+
+impl TexturePackerSheet {
+    /// Imports the sheet, creating one Sprite per animation by cropping each
+    /// frame out of the `sheet` image. Frames are trimmed and rotated back to
+    /// their original size and orientation.
+    ///
+    /// TexturePacker has no notion of animations, so frames are grouped by
+    /// their [`animation_name`](Self::animation_name) and ordered by their
+    /// trailing frame number. Each Sprite is named `{base_name}_{animation}`
+    /// in snake case, or just `{animation}` if `base_name` is empty. Sprites
+    /// play at `fps` frames per second and are assigned to `texture_group`.
+    ///
+    /// If the first frame of an animation has a pivot, the Sprite's origin is
+    /// set to [`Origin::Custom`](super::Origin::Custom) at that pivot.
+    ///
+    /// # Errors
+    ///
+    /// Errors if a frame does not fit within the `sheet`, if the frames of an
+    /// animation differ in size, if a Sprite's name would not be a valid
+    /// resource name, or if two animations, such as `Walk` and `walk`, would
+    /// make Sprites with the same name.
+    pub fn import(
+        &self,
+        base_name: &str,
+        sheet: &RgbaImage,
+        texture_group: &TexturePath,
+        fps: f32,
+    ) -> Result<Vec<ImportedSprite>, SpriteImportError> {
+        use heck::ToSnakeCase;
+
+        let mut animations: Vec<(&str, Vec<&SheetFrame>)> = vec![];
+        for frame in self.frames.iter() {
+            let animation = Self::animation_name(&frame.filename);
+            match animations.iter_mut().find(|(name, _)| *name == animation) {
+                Some((_, frames)) => frames.push(frame),
+                None => animations.push((animation, vec![frame])),
+            }
+        }
+
+        let validator = ResourceNameValidator::new();
+        let mut output: Vec<ImportedSprite> = Vec::with_capacity(animations.len());
+
+        for (animation, mut frames) in animations {
+            let name = if base_name.is_empty() {
+                animation.to_snake_case()
+            } else {
+                format!("{}_{}", base_name, animation.to_snake_case())
+            };
+            if !validator.is_valid(&name) {
+                return Err(SpriteImportError::InvalidName(name));
+            }
+            if output.iter().any(|v| v.sprite.common_data.name == name) {
+                return Err(SpriteImportError::DuplicateName(name));
+            }
+
+            frames.sort_by_key(|frame| Self::frame_number(&frame.filename));
+
+            let images = frames
+                .iter()
+                .map(|frame| frame.extract(sheet))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let mut imported = ImportedSprite::from_frames(&name, images, &[])?;
+            let sprite = &mut imported.sprite;
+            sprite.sequence.playback_speed = fps;
+            sprite.texture_group_id = texture_group.clone();
+
+            if let Some(pivot) = frames[0].pivot {
+                sprite.set_custom_origin(
+                    (pivot.x * sprite.width.get() as f64).round() as i32,
+                    (pivot.y * sprite.height.get() as f64).round() as i32,
+                );
+            }
+
+            output.push(imported);
+        }
+
+        Ok(output)
+    }
+
+    /// The name of the animation a frame belongs to, which is its filename
+    /// without its extension, trailing frame number, or the separator before
+    /// that number. `"walk/0001.png"`, `"walk_01.png"`, and `"walk-1"` are
+    /// all frames of `"walk"`.
+    pub fn animation_name(filename: &str) -> &str {
+        let stem = Self::stem(filename);
+        let without_number = stem
+            .trim_end_matches(|c: char| c.is_ascii_digit())
+            .trim_end_matches(['_', '-', ' ', '/']);
+
+        if without_number.is_empty() {
+            stem
+        } else {
+            without_number
+        }
+    }
+
+    fn frame_number(filename: &str) -> Option<u64> {
+        let stem = Self::stem(filename);
+        let digits = stem.len() - stem.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+
+        stem[stem.len() - digits..].parse().ok()
+    }
+
+    fn stem(filename: &str) -> &str {
+        match filename.rsplit_once('.') {
+            Some((stem, extension)) if !extension.contains('/') => stem,
+            _ => filename,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprite::Origin;
+    use pretty_assertions::assert_eq;
+
+    const SHEET: &str = r#"{"frames": [
+        {
+            "filename": "tree/idle_02.png",
+            "frame": {"x":0,"y":0,"w":4,"h":2},
+            "rotated": true,
+            "trimmed": true,
+            "spriteSourceSize": {"x":1,"y":2,"w":4,"h":2},
+            "sourceSize": {"w":6,"h":6},
+            "pivot": {"x":0.5,"y":1}
+        },
+        {
+            "filename": "tree/idle_01.png",
+            "frame": {"x":2,"y":0,"w":6,"h":6},
+            "rotated": false,
+            "trimmed": false,
+            "spriteSourceSize": {"x":0,"y":0,"w":6,"h":6},
+            "sourceSize": {"w":6,"h":6},
+            "pivot": {"x":0.5,"y":1}
+        },
+        {
+            "filename": "rock.png",
+            "frame": {"x":8,"y":0,"w":2,"h":2},
+            "rotated": false,
+            "trimmed": false,
+            "spriteSourceSize": {"x":0,"y":0,"w":2,"h":2},
+            "sourceSize": {"w":2,"h":2}
+        }],
+        "meta": {
+            "app": "https://www.codeandweb.com/texturepacker",
+            "version": "1.0",
+            "image": "sheet.png",
+            "format": "RGBA8888",
+            "size": {"w":10,"h":6},
+            "scale": "1"
+        }
+    }"#;
+
+    #[test]
+    fn import_animations() {
+        let sheet: TexturePackerSheet = serde_json::from_str(SHEET).unwrap();
+        let texture_group = TexturePath {
+            name: "Trees".to_string(),
+            path: crate::TexturePathLocation("texturegroups/Trees".to_string()),
+        };
+
+        let sprites = sheet
+            .import("spr", &RgbaImage::new(10, 6), &texture_group, 12.0)
+            .unwrap();
+        assert_eq!(sprites.len(), 2);
+
+        let tree = &sprites[0];
+        assert_eq!(tree.sprite.common_data.name, "spr_tree_idle");
+        assert_eq!(tree.images.len(), 2);
+        assert_eq!(tree.sprite.width.get(), 6);
+        assert_eq!(tree.sprite.origin, Origin::Custom);
+        assert_eq!(
+            (tree.sprite.sequence.xorigin, tree.sprite.sequence.yorigin),
+            (3, 6)
+        );
+        assert_eq!(tree.sprite.sequence.playback_speed, 12.0);
+        assert_eq!(tree.sprite.texture_group_id, texture_group);
+
+        let rock = &sprites[1];
+        assert_eq!(rock.sprite.common_data.name, "spr_rock");
+        assert_eq!(rock.sprite.origin, Origin::TopLeft);
+    }
+
+    #[test]
+    fn animation_names() {
+        assert_eq!(TexturePackerSheet::animation_name("walk/0001.png"), "walk");
+        assert_eq!(TexturePackerSheet::animation_name("walk_01.png"), "walk");
+        assert_eq!(TexturePackerSheet::animation_name("walk-1"), "walk");
+        assert_eq!(TexturePackerSheet::animation_name("tree.png"), "tree");
+        assert_eq!(TexturePackerSheet::animation_name("0001.png"), "0001");
+        assert_eq!(TexturePackerSheet::animation_name("v1.2/rock"), "v1.2/rock");
+    }
+}